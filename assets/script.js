@@ -1,37 +1,299 @@
-/*ORIGIN*/
-if (ORIGIN === null || window.location.origin === ORIGIN) {
-  const shortcuts = new Map();
-  window.addEventListener('keydown', (e) => {
-    const eKey = e.key.toLowerCase();
-    const set = shortcuts.get(eKey);
-    if (set) {
-      const flags = toFlags({
-        altKey: e.altKey,
-        ctrlKey: e.ctrlKey,
-        metaKey: e.metaKey,
-        shiftKey: e.shiftKey,
+{
+  let options = null;
+  let active = false;
+  // In allow list mode, every restricted key is prevented unless it is allowed.
+  let allowList = false;
+  const allowed = { keys: new Map(), codes: new Map() };
+  // Shortcuts are grouped by the options of the listeners that prevent them.
+  const slots = new Map();
+  const handled = new WeakSet();
+  // `beforeinput` is not a keyboard event, so it is matched against the key being held down.
+  let pressed = null;
+  window.addEventListener('keydown', (e) => (pressed = e), { capture: true });
+  window.addEventListener('keyup', () => (pressed = null), { capture: true });
+  function getSlot(listener = options.listener) {
+    const id = `${listener.target}:${listener.capture}:${listener.propagation}`;
+    let slot = slots.get(id);
+    if (!slot) {
+      slot = {
+        ...listener,
+        keyboard: new Map(),
+        pointers: new Map(),
+        names: new Set(),
+        sequences: [],
+      };
+      slots.set(id, slot);
+      getGroup(slot, 'keydown');
+    }
+    return slot;
+  }
+  // Keyboard shortcuts of a slot are grouped by the type of the events they prevent.
+  function getGroup(slot, type) {
+    let group = slot.keyboard.get(type);
+    if (!group) {
+      group = { keys: new Map(), codes: new Map() };
+      slot.keyboard.set(type, group);
+    }
+    if (!slot.names.has(type)) {
+      slot.names.add(type);
+      addListener(slot, type, (e) => onKeyboard(slot, type, e));
+    }
+    return group;
+  }
+  function addListener(slot, type, listener) {
+    const target = slot.target === 'document' ? document : window;
+    // Wheel listeners are passive by default, so they could not prevent the zoom.
+    target.addEventListener(type, listener, { capture: slot.capture, passive: false });
+  }
+  function onKeyboard(slot, type, e) {
+    if (!active) return;
+    const source = type === 'beforeinput' ? pressed : e;
+    if (!source) return;
+    const flags = toFlags(source);
+    const eKey = source.key?.toLowerCase();
+    const eCode = source.code?.toLowerCase();
+    const repeat = Boolean(source.repeat);
+    // Sequences keep track of every event, even those already handled by another slot.
+    const sequence = type === 'keydown' ? advance(slot, e, flags, eKey, eCode) : null;
+    if (handled.has(e)) return;
+    if (sequence) {
+      prevent(slot, e, sequence.display);
+      return;
+    }
+    if (allowList) {
+      if (matches(allowed.keys.get(eKey), flags, e, repeat)) return;
+      if (matches(allowed.codes.get(eCode), flags, e, repeat)) return;
+    }
+    const group = slot.keyboard.get(type);
+    let display = null;
+    let match = matches(group?.keys.get(eKey), flags, e, repeat);
+    if (match) {
+      display = match.display ?? toDisplay(eKey, flags);
+    } else if ((match = matches(group?.codes.get(eCode), flags, e, repeat))) {
+      display = match.display ?? toDisplay(`code:${eCode}`, flags);
+    } else if (type === 'keydown' && allowList && slot === getSlot() && isRestricted(e)) {
+      display = toDisplay(eKey, flags);
+    }
+    if (display !== null && !isExempt(e, options)) {
+      prevent(slot, e, display, match?.flag);
+      if ((match?.handler ?? null) !== null) handle(match.handler);
+    }
+  }
+  // Each sequence waits for its next step until the timeout, and starts over if anything else is
+  // pressed. Only the last step is prevented, unless the sequence prevents all of them.
+  function advance(slot, e, flags, eKey, eCode) {
+    if (slot.sequences.length === 0 || MODIFIER_KEYS.has(e.key)) return null;
+    const now = Date.now();
+    let result = null;
+    for (const sequence of slot.sequences) {
+      if (now > sequence.deadline) sequence.position = 0;
+      if (isExempt(e, sequence) || isExempt(e, options)) {
+        sequence.position = 0;
+        continue;
+      }
+      const isStep = (position) => sequence.steps[position].some((step) => {
+        return ((step.bitmap >> flags) & 1) && step.key === (step.code ? eCode : eKey);
       });
-      if (set.has(flags)) {
-        e.preventDefault();
+      if (!isStep(sequence.position)) {
+        if (sequence.position === 0 || !isStep(0)) {
+          sequence.position = 0;
+          continue;
+        }
+        sequence.position = 0;
+      }
+      sequence.position++;
+      sequence.deadline = now + sequence.timeout;
+      if (sequence.position === sequence.steps.length) {
+        sequence.position = 0;
+        result = { display: sequence.display };
+      } else if (sequence.preventAllSteps) {
+        result ??= { display: null };
       }
     }
-  });
-  function onKey(key, options = {}) {
-    const _key = key.toLowerCase();
-    let set = shortcuts.get(_key);
-    const flags = toFlags(options);
-    if (set) {
-      set.add(flags);
+    return result;
+  }
+  // The same event may reach the listeners of several slots, but it is only handled once.
+  function prevent(slot, e, shortcut, flag = null) {
+    handled.add(e);
+    e.preventDefault();
+    if (slot.propagation === 'stopImmediate') {
+      e.stopImmediatePropagation();
+    } else if (slot.propagation === 'stop') {
+      e.stopPropagation();
+    }
+    // Intermediate steps of a sequence are prevented silently.
+    if (shortcut !== null) {
+      notify(shortcut);
+      dispatchBlocked(e, shortcut, flag);
+    }
+  }
+  // Each key has a bitmap with one bit for each of the 16 modifier combinations that are always
+  // prevented, along with the rules of the shortcuts that have exemptions, depend on repetition
+  // or have a handler. Matching rules with a handler take precedence.
+  function matches(entry, flags, e, repeat) {
+    if (!entry) return null;
+    let match = (entry.always >> flags) & 1 ? entry.matches[flags] : null;
+    for (const rule of entry.rules) {
+      if (rule.repeat === 'only' ? !repeat : rule.repeat === 'never' && repeat) continue;
+      if (((rule.bitmap >> flags) & 1) && !isExempt(e, rule)) {
+        if (rule.match.handler !== null) return rule.match;
+        match ??= rule.match;
+      }
+    }
+    return match;
+  }
+  // What is known about the shortcut that prevented an event.
+  function toMatch(shortcut) {
+    return {
+      handler: shortcut.handler ?? null,
+      display: shortcut.display ?? null,
+      flag: shortcut.flag ?? null,
+    };
+  }
+  const MODIFIER_KEYS = new Set([
+    'Alt', 'AltGraph', 'CapsLock', 'Control', 'Fn', 'FnLock', 'Hyper', 'Meta', 'NumLock', 'OS',
+    'ScrollLock', 'Shift', 'Super', 'Symbol', 'SymbolLock',
+  ]);
+  const FUNCTION_KEY = /^F([1-9]|1[0-9]|2[0-4])$/;
+  const IS_APPLE = /^(Mac|iPhone|iPad)/.test(navigator.platform ?? '');
+  // Characters typed with `AltGr` report both `ctrlKey` and `altKey` on Windows,
+  // and `Option` is how characters such as `™` are typed on Apple platforms,
+  // but both are plain typing.
+  function isRestricted(e) {
+    if (MODIFIER_KEYS.has(e.key)) return false;
+    if (FUNCTION_KEY.test(e.key ?? '')) return true;
+    if (e.getModifierState?.('AltGraph')) return false;
+    if (IS_APPLE && e.altKey && !e.ctrlKey && !e.metaKey && isPrintable(e.key)) return false;
+    return Boolean(e.ctrlKey || e.altKey || e.metaKey);
+  }
+  function isPrintable(key = '') {
+    return key === 'Dead' || [...key].length === 1;
+  }
+  function onKey(shortcut) {
+    const slot = getSlot(shortcut.listener);
+    for (const type of shortcut.events ?? ['keydown']) {
+      addKey(getGroup(slot, type), shortcut);
+    }
+  }
+  function addKey(group, shortcut) {
+    // Physical keys are matched against `KeyboardEvent.code` instead.
+    const shortcuts = shortcut.code ? group.codes : group.keys;
+    const _key = shortcut.key.toLowerCase();
+    let entry = shortcuts.get(_key);
+    if (!entry) {
+      // The first shortcut to always prevent each modifier combination is the one reported.
+      entry = { always: 0, matches: [], rules: [] };
+      shortcuts.set(_key, entry);
+    }
+    const bitmap = toBitmap(shortcut);
+    const repeat = shortcut.repeat ?? 'any';
+    const match = toMatch(shortcut);
+    if (hasExemptions(shortcut) || repeat !== 'any' || match.handler !== null) {
+      entry.rules.push({ bitmap, repeat, match, ...toRule(shortcut) });
     } else {
-      set = new Set([flags]);
-      shortcuts.set(_key, set);
+      entry.always |= bitmap;
+      for (let flags = 0; flags < 16; flags++) {
+        if ((bitmap >> flags) & 1) entry.matches[flags] ??= match;
+      }
+    }
+  }
+  function toBitmap(shortcut) {
+    const required = toFlags(toModifiers(shortcut.modifiers));
+    const optional = toFlags(toModifiers(shortcut.optional));
+    let bitmap = 0;
+    for (let flags = 0; flags < 16; flags++) {
+      if ((flags & ~optional) === required) bitmap |= 1 << flags;
     }
+    return bitmap;
   }
-  function onPointer(name) {
-    window.addEventListener(name, (e) => {
-      e.preventDefault();
+  function onSequence(sequence) {
+    const slot = getSlot(sequence.listener);
+    slot.sequences.push({
+      ...toRule(sequence),
+      steps: sequence.steps.map((step) => {
+        return step.map((it) => {
+          return { key: it.key.toLowerCase(), code: Boolean(it.code), bitmap: toBitmap(it) };
+        });
+      }),
+      timeout: sequence.timeout,
+      preventAllSteps: Boolean(sequence.preventAllSteps),
+      display: sequence.display,
+      position: 0,
+      deadline: 0,
     });
   }
+  function hasExemptions(rule) {
+    return Boolean(rule.skipEditable) || (rule.skipSelectors?.length ?? 0) > 0;
+  }
+  function toRule(exemptions) {
+    return {
+      skipEditable: Boolean(exemptions.skipEditable),
+      skipSelectors: exemptions.skipSelectors ?? [],
+    };
+  }
+  // Elements marked with `data-prevent-default="off"` are always exempt.
+  // The composed path is used so that elements inside a shadow root are also checked.
+  function isExempt(e, rule) {
+    const path = (e.composedPath?.() ?? []).filter((it) => it?.nodeType === 1);
+    if (path.length === 0 && e.target?.nodeType === 1) path.push(e.target);
+    if (path.some((it) => it.getAttribute?.('data-prevent-default') === 'off')) return true;
+    if (rule.skipEditable && path.length > 0 && isEditable(path[0])) return true;
+    return (rule.skipSelectors ?? []).some((selector) => {
+      return path.some((it) => {
+        try {
+          return it.matches(selector);
+        } catch {
+          return false;
+        }
+      });
+    });
+  }
+  const NON_TEXT_INPUTS = new Set([
+    'button', 'checkbox', 'color', 'file', 'hidden', 'image', 'radio', 'range', 'reset', 'submit',
+  ]);
+  function isEditable(target) {
+    if (target.isContentEditable) return true;
+    switch (target.tagName) {
+      case 'TEXTAREA':
+      case 'SELECT':
+        return !target.disabled;
+      case 'INPUT':
+        return !target.disabled && !target.readOnly && !NON_TEXT_INPUTS.has(target.type);
+      default:
+        return false;
+    }
+  }
+  const POINTER_EVENTS = {
+    auxclick: [['auxclick'], (e) => e.button === 1],
+    mouseback: [['mouseup'], (e) => e.button === 3],
+    mouseforward: [['mouseup'], (e) => e.button === 4],
+    zoomwheel: [['wheel'], (e) => e.ctrlKey],
+    zoomgesture: [['gesturestart', 'gesturechange'], () => true],
+  };
+  function onPointer(shortcut) {
+    const name = shortcut.event;
+    const slot = getSlot(shortcut.listener);
+    let rules = slot.pointers.get(name);
+    if (!rules) {
+      rules = [];
+      slot.pointers.set(name, rules);
+    }
+    rules.push({ flag: shortcut.flag, ...toRule(shortcut) });
+    if (!slot.names.has(name)) {
+      slot.names.add(name);
+      const [types, matches] = POINTER_EVENTS[name] ?? [[name], () => true];
+      for (const type of types) {
+        addListener(slot, type, (e) => {
+          if (!active || handled.has(e) || !matches(e)) return;
+          const rules = slot.pointers.get(name) ?? [];
+          const rule = rules.find((it) => !isExempt(e, it));
+          if (rule && !isExempt(e, options)) {
+            prevent(slot, e, `pointer:${name}`, rule.flag);
+          }
+        });
+      }
+    }
+  }
   function toFlags(options) {
     let flags = 0;
     if (options.altKey) flags |= (1 << 0);
@@ -40,5 +302,68 @@ if (ORIGIN === null || window.location.origin === ORIGIN) {
     if (options.shiftKey) flags |= (1 << 3);
     return flags;
   }
-  /*SCRIPT*/
+  function toModifiers(names = []) {
+    return Object.fromEntries(names.map((it) => [it, true]));
+  }
+  function toDisplay(key, flags) {
+    let display = 'keyboard:';
+    if (flags & (1 << 1)) display += 'ctrl+';
+    if (flags & (1 << 3)) display += 'shift+';
+    if (flags & (1 << 0)) display += 'alt+';
+    if (flags & (1 << 2)) display += 'meta+';
+    return display + key;
+  }
+  function notify(shortcut) {
+    if (options.notify) {
+      window.__TAURI_INTERNALS__
+        ?.invoke('plugin:prevent-default|blocked', { shortcut })
+        ?.catch(() => {});
+    }
+  }
+  function dispatchBlocked(e, shortcut, flag) {
+    if (options.dispatch) {
+      const detail = { shortcut, flag: flag ?? null, event: e };
+      const init = { bubbles: true, composed: true, detail };
+      (e.target ?? window).dispatchEvent?.(new CustomEvent('prevent-default:blocked', init));
+    }
+  }
+  function handle(id) {
+    window.__TAURI_INTERNALS__
+      ?.invoke('plugin:prevent-default|handle', { id })
+      ?.catch(() => {});
+  }
+  function isAllowed(origins) {
+    const origin = window.location.origin;
+    return (
+      (origins.allow.length === 0 || origins.allow.some((it) => new RegExp(it).test(origin))) &&
+      !origins.deny.some((it) => new RegExp(it).test(origin))
+    );
+  }
+  function update(table, _options) {
+    options = _options;
+    active = isAllowed(options.origins);
+    for (const slot of slots.values()) {
+      slot.keyboard.clear();
+      slot.pointers.clear();
+      slot.sequences = [];
+    }
+    allowed.keys.clear();
+    allowed.codes.clear();
+    allowList = table.mode === 'allowList';
+    if (allowList) getSlot();
+    (table.allow ?? []).forEach((shortcut) => addKey(allowed, shortcut));
+    table.keys.forEach(onKey);
+    table.pointers.forEach(onPointer);
+    (table.sequences ?? []).forEach(onSequence);
+  }
+  Object.defineProperty(window, '__TAURI_PLUGIN_PREVENT_DEFAULT__', {
+    value: Object.freeze({ update }),
+  });
+  /*CONFIG*/
+  const metadata = window.__TAURI_INTERNALS__?.metadata;
+  const labels = [metadata?.currentWebview?.label, metadata?.currentWindow?.label];
+  const entry = CONFIG.windows.find(([pattern]) => {
+    return labels.some((label) => typeof label === 'string' && new RegExp(pattern).test(label));
+  });
+  update(entry ? entry[1] : CONFIG.default, CONFIG.options);
 }
@@ -0,0 +1,326 @@
+// Behavior tests for the injected script, run with `node --test assets/`.
+// The script is evaluated against a minimal fake DOM, with a hand-written shortcut table.
+const assert = require('node:assert/strict');
+const fs = require('node:fs');
+const path = require('node:path');
+const test = require('node:test');
+const vm = require('node:vm');
+
+const SOURCE = fs.readFileSync(path.join(__dirname, 'script.js'), 'utf8');
+
+const OPTIONS = {
+  origins: { allow: [], deny: [] },
+  notify: false,
+  skipEditable: false,
+  skipSelectors: [],
+  listener: { target: 'window', capture: false, propagation: 'continue' },
+};
+
+// Only what the script checks on the elements of the composed path.
+function element({ tagName = 'DIV', attributes = {}, selectors = [], ...props } = {}) {
+  return {
+    nodeType: 1,
+    tagName,
+    ...props,
+    getAttribute: (name) => attributes[name] ?? null,
+    matches: (selector) => selectors.includes(selector),
+  };
+}
+
+function load(table, { options = {}, platform = 'Win32' } = {}) {
+  const listeners = new Map();
+  const calls = [];
+  // Sequences read the time through `Date.now`, which is advanced by hand.
+  let now = 0;
+  const addEventListener = (type, listener) => {
+    if (!listeners.has(type)) listeners.set(type, []);
+    listeners.get(type).push(listener);
+  };
+
+  const window = {
+    location: { origin: 'tauri://localhost' },
+    addEventListener,
+    dispatchEvent: (e) => calls.push(['dispatch', e]),
+    __TAURI_INTERNALS__: {
+      metadata: { currentWebview: { label: 'main' }, currentWindow: { label: 'main' } },
+      invoke: (command, args) => {
+        // Objects created inside the context have their own prototypes.
+        calls.push([command, JSON.parse(JSON.stringify(args))]);
+        return Promise.resolve();
+      },
+    },
+  };
+
+  class CustomEvent {
+    constructor(type, init) {
+      this.type = type;
+      Object.assign(this, init);
+    }
+  }
+
+  const config = { options: { ...OPTIONS, ...options }, windows: [], default: table };
+  const source = SOURCE.replace('/*CONFIG*/', `const CONFIG=${JSON.stringify(config)};`);
+  const context = {
+    window,
+    document: { addEventListener },
+    navigator: { platform },
+    CustomEvent,
+    Date: { now: () => now },
+  };
+
+  vm.runInNewContext(source, context);
+
+  // Returns whether the default action of the event was prevented.
+  function dispatch(type, init = {}) {
+    let prevented = false;
+    const e = {
+      type,
+      key: '',
+      code: '',
+      stopPropagation: () => calls.push(['stopPropagation', type]),
+      stopImmediatePropagation: () => calls.push(['stopImmediatePropagation', type]),
+      composedPath: () => [],
+      ...init,
+      preventDefault: () => (prevented = true),
+    };
+
+    for (const listener of listeners.get(type) ?? []) listener(e);
+    return prevented;
+  }
+
+  function wait(ms) {
+    now += ms;
+  }
+
+  return { dispatch, wait, calls };
+}
+
+test('allow list mode leaves typing with Option alone on Apple platforms', () => {
+  const table = { keys: [], pointers: [], mode: 'allowList' };
+  const mac = load(table, { platform: 'MacIntel' });
+  assert.equal(mac.dispatch('keydown', { key: '™', altKey: true }), false);
+  assert.equal(mac.dispatch('keydown', { key: 'Dead', altKey: true }), false);
+  assert.equal(mac.dispatch('keydown', { key: 'ArrowLeft', altKey: true }), true);
+  assert.equal(mac.dispatch('keydown', { key: 'k', altKey: true, metaKey: true }), true);
+  assert.equal(mac.dispatch('keydown', { key: 'k', metaKey: true }), true);
+
+  const windows = load(table);
+  assert.equal(windows.dispatch('keydown', { key: '™', altKey: true }), true);
+  assert.equal(windows.dispatch('keydown', { key: 'k', ctrlKey: true }), true);
+  assert.equal(windows.dispatch('keydown', { key: 'k' }), false);
+});
+
+test('the plugin is notified with the display of the matched shortcut', () => {
+  const display = 'keyboard:cmdorctrl+f|code:keyf';
+  const any = ['ctrlKey', 'shiftKey', 'altKey', 'metaKey'];
+  const table = {
+    keys: [
+      { key: 'f', modifiers: ['ctrlKey'], display },
+      { key: 'KeyF', code: true, modifiers: ['ctrlKey'], display },
+      { key: 'F5', optional: any, display: 'keyboard:*+f5' },
+    ],
+    pointers: [],
+    mode: 'allowList',
+  };
+
+  const { dispatch, calls } = load(table, { options: { notify: true } });
+  assert.equal(dispatch('keydown', { key: 'f', code: 'KeyF', ctrlKey: true }), true);
+  assert.equal(dispatch('keydown', { key: 'u', code: 'KeyF', ctrlKey: true }), true);
+  assert.equal(dispatch('keydown', { key: 'F5', shiftKey: true }), true);
+  assert.equal(dispatch('keydown', { key: 'k', ctrlKey: true }), true);
+  assert.deepEqual(calls, [
+    ['plugin:prevent-default|blocked', { shortcut: display }],
+    ['plugin:prevent-default|blocked', { shortcut: display }],
+    ['plugin:prevent-default|blocked', { shortcut: 'keyboard:*+f5' }],
+    ['plugin:prevent-default|blocked', { shortcut: 'keyboard:ctrl+k' }],
+  ]);
+});
+
+test('the blocked event holds the display and the flag of the matched shortcut', () => {
+  const display = 'keyboard:cmdorctrl+p|code:keyp';
+  const table = {
+    keys: [
+      { key: 'p', modifiers: ['ctrlKey'], display, flag: 'PRINT' },
+      { key: 'k', modifiers: ['ctrlKey'], display: 'keyboard:ctrl+k' },
+    ],
+    pointers: [{ event: 'contextmenu', flag: 'CONTEXT_MENU' }],
+  };
+
+  const { dispatch, calls } = load(table, { options: { dispatch: true } });
+  dispatch('keydown', { key: 'p', ctrlKey: true });
+  dispatch('keydown', { key: 'k', ctrlKey: true });
+  dispatch('contextmenu');
+
+  const events = calls.map(([, e]) => e);
+  assert.deepEqual(
+    events.map((e) => [e.type, e.bubbles, e.detail.shortcut, e.detail.flag, e.detail.event.type]),
+    [
+      ['prevent-default:blocked', true, display, 'PRINT', 'keydown'],
+      ['prevent-default:blocked', true, 'keyboard:ctrl+k', null, 'keydown'],
+      ['prevent-default:blocked', true, 'pointer:contextmenu', 'CONTEXT_MENU', 'contextmenu'],
+    ],
+  );
+});
+
+test('shortcuts only prevent the events and repetitions they are set for', () => {
+  const table = {
+    keys: [
+      { key: 'F5', repeat: 'never' },
+      { key: 'F6', repeat: 'only' },
+      { key: 'F7', events: ['keyup'] },
+      { key: 'F8', events: ['keydown', 'keyup'] },
+    ],
+    pointers: [],
+  };
+
+  const { dispatch } = load(table);
+  assert.equal(dispatch('keydown', { key: 'F5' }), true);
+  assert.equal(dispatch('keydown', { key: 'F5', repeat: true }), false);
+  assert.equal(dispatch('keydown', { key: 'F6' }), false);
+  assert.equal(dispatch('keydown', { key: 'F6', repeat: true }), true);
+  assert.equal(dispatch('keydown', { key: 'F7' }), false);
+  assert.equal(dispatch('keyup', { key: 'F7' }), true);
+  assert.equal(dispatch('keydown', { key: 'F8' }), true);
+  assert.equal(dispatch('keyup', { key: 'F8' }), true);
+});
+
+test('sequences only prevent their last step, unless they prevent all of them', () => {
+  const steps = [[{ key: 'k', modifiers: ['ctrlKey'] }], [{ key: 's' }]];
+  const display = 'sequence:ctrl+k s';
+  const table = { keys: [], pointers: [], sequences: [{ steps, timeout: 500, display }] };
+
+  const { dispatch, calls } = load(table, { options: { notify: true } });
+  assert.equal(dispatch('keydown', { key: 'k', ctrlKey: true }), false);
+  assert.equal(dispatch('keydown', { key: 'Control', ctrlKey: true }), false);
+  assert.equal(dispatch('keydown', { key: 's' }), true);
+  assert.equal(dispatch('keydown', { key: 's' }), false);
+  assert.deepEqual(calls, [['plugin:prevent-default|blocked', { shortcut: display }]]);
+
+  table.sequences[0].preventAllSteps = true;
+  const all = load(table, { options: { notify: true } });
+  assert.equal(all.dispatch('keydown', { key: 'k', ctrlKey: true }), true);
+  assert.equal(all.dispatch('keydown', { key: 's' }), true);
+  assert.deepEqual(all.calls, [['plugin:prevent-default|blocked', { shortcut: display }]]);
+});
+
+test('sequences start over after the timeout or a wrong key', () => {
+  const steps = [[{ key: 'k', modifiers: ['ctrlKey'] }], [{ key: 's' }]];
+  const table = {
+    keys: [],
+    pointers: [],
+    sequences: [{ steps, timeout: 500, display: 'sequence:ctrl+k s' }],
+  };
+
+  const { dispatch, wait } = load(table);
+  dispatch('keydown', { key: 'k', ctrlKey: true });
+  wait(501);
+  assert.equal(dispatch('keydown', { key: 's' }), false);
+
+  dispatch('keydown', { key: 'k', ctrlKey: true });
+  wait(500);
+  assert.equal(dispatch('keydown', { key: 's' }), true);
+
+  dispatch('keydown', { key: 'k', ctrlKey: true });
+  dispatch('keydown', { key: 'x' });
+  assert.equal(dispatch('keydown', { key: 's' }), false);
+
+  // A wrong key that is the first step starts the sequence again.
+  dispatch('keydown', { key: 'k', ctrlKey: true });
+  dispatch('keydown', { key: 'k', ctrlKey: true });
+  assert.equal(dispatch('keydown', { key: 's' }), true);
+});
+
+test('exempt elements are found along the composed path', () => {
+  const table = {
+    keys: [
+      { key: 'f', modifiers: ['ctrlKey'] },
+      { key: 'g', modifiers: ['ctrlKey'], skipSelectors: ['[data-editor]'] },
+      { key: 'h', modifiers: ['ctrlKey'], skipEditable: true },
+    ],
+    pointers: [{ event: 'contextmenu', skipSelectors: ['.native-menu'] }],
+  };
+
+  const { dispatch } = load(table);
+  const inside = (...path) => ({ target: path[0], composedPath: () => path });
+  const ctrl = (key, init) => dispatch('keydown', { key, ctrlKey: true, ...init });
+  const host = element({ attributes: { 'data-prevent-default': 'off' } });
+  const editor = element({ selectors: ['[data-editor]'] });
+  const input = element({ tagName: 'INPUT', type: 'text' });
+  const menu = element({ selectors: ['.native-menu'] });
+
+  // The element inside a shadow root is the target, and its host is further along the path.
+  assert.equal(ctrl('f', inside(element(), host)), false);
+  assert.equal(ctrl('f', inside(editor)), true);
+  assert.equal(ctrl('g', inside(element(), editor)), false);
+  assert.equal(ctrl('g', inside(input)), true);
+  assert.equal(ctrl('h', inside(input)), false);
+  assert.equal(ctrl('h', inside(element(), input)), true);
+  assert.equal(dispatch('contextmenu', inside(menu)), false);
+  assert.equal(dispatch('contextmenu', inside(editor)), true);
+
+  // Without a composed path, only the target is checked.
+  assert.equal(ctrl('f', { target: host }), false);
+});
+
+test('skip selectors of the options apply to every shortcut', () => {
+  const table = {
+    keys: [{ key: 'f', modifiers: ['ctrlKey'] }],
+    pointers: [{ event: 'contextmenu' }],
+  };
+
+  const { dispatch } = load(table, { options: { skipSelectors: ['.native'] } });
+  const native = element({ selectors: ['.native'] });
+  assert.equal(dispatch('keydown', { key: 'f', ctrlKey: true, target: native }), false);
+  assert.equal(dispatch('contextmenu', { target: native }), false);
+  assert.equal(dispatch('keydown', { key: 'f', ctrlKey: true, target: element() }), true);
+});
+
+test('each listener slot stops the propagation as configured, and handles an event once', () => {
+  const stop = { target: 'window', capture: true, propagation: 'stop' };
+  const stopImmediate = { target: 'document', capture: true, propagation: 'stopImmediate' };
+  const table = {
+    keys: [
+      { key: 'a', modifiers: ['ctrlKey'], listener: stop },
+      { key: 'b', modifiers: ['ctrlKey'], listener: stopImmediate },
+      { key: 'c', modifiers: ['ctrlKey'] },
+      { key: 'd', modifiers: ['ctrlKey'], listener: stop },
+      { key: 'd', modifiers: ['ctrlKey'], listener: stopImmediate },
+    ],
+    pointers: [],
+  };
+
+  const { dispatch, calls } = load(table, { options: { notify: true } });
+  assert.equal(dispatch('keydown', { key: 'a', ctrlKey: true }), true);
+  assert.equal(dispatch('keydown', { key: 'b', ctrlKey: true }), true);
+  assert.equal(dispatch('keydown', { key: 'c', ctrlKey: true }), true);
+  assert.equal(dispatch('keydown', { key: 'd', ctrlKey: true }), true);
+  assert.deepEqual(calls, [
+    ['stopPropagation', 'keydown'],
+    ['plugin:prevent-default|blocked', { shortcut: 'keyboard:ctrl+a' }],
+    ['stopImmediatePropagation', 'keydown'],
+    ['plugin:prevent-default|blocked', { shortcut: 'keyboard:ctrl+b' }],
+    ['plugin:prevent-default|blocked', { shortcut: 'keyboard:ctrl+c' }],
+    ['stopPropagation', 'keydown'],
+    ['plugin:prevent-default|blocked', { shortcut: 'keyboard:ctrl+d' }],
+  ]);
+});
+
+test('shortcuts with a handler invoke it, and take precedence over those without one', () => {
+  const table = {
+    keys: [
+      { key: 'p', modifiers: ['ctrlKey'], display: 'keyboard:cmdorctrl+p' },
+      { key: 'p', modifiers: ['ctrlKey'], display: 'keyboard:ctrl+p', handler: 0 },
+      { key: 'k', modifiers: ['ctrlKey'] },
+    ],
+    pointers: [],
+  };
+
+  const { dispatch, calls } = load(table, { options: { notify: true } });
+  assert.equal(dispatch('keydown', { key: 'p', ctrlKey: true }), true);
+  assert.equal(dispatch('keydown', { key: 'k', ctrlKey: true }), true);
+  assert.deepEqual(calls, [
+    ['plugin:prevent-default|blocked', { shortcut: 'keyboard:ctrl+p' }],
+    ['plugin:prevent-default|handle', { id: 0 }],
+    ['plugin:prevent-default|blocked', { shortcut: 'keyboard:ctrl+k' }],
+  ]);
+});
@@ -0,0 +1,5 @@
+const COMMANDS: &[&str] = &["blocked", "handle"];
+
+fn main() {
+  tauri_plugin::Builder::new(COMMANDS).build();
+}
@@ -0,0 +1,25 @@
+use crate::error::Result;
+use crate::event::BlockedEvent;
+use crate::origin;
+use crate::PreventDefault;
+use tauri::{command, AppHandle, Runtime, Webview};
+
+#[command]
+#[allow(clippy::needless_pass_by_value)]
+pub(crate) fn blocked<R: Runtime>(
+  app: AppHandle<R>,
+  webview: Webview<R>,
+  shortcut: String,
+) -> Result<()> {
+  // The origin is not taken from the script, as any page could claim another one.
+  let origin = origin::of_url(&webview.url()?);
+  let window = webview.window_ref().label().to_owned();
+  let event = BlockedEvent::new(shortcut, window, webview.label().to_owned(), origin);
+  app.prevent_default().blocked(&app, event)
+}
+
+#[command]
+#[allow(clippy::needless_pass_by_value)]
+pub(crate) fn handle<R: Runtime>(webview: Webview<R>, id: usize) {
+  webview.prevent_default().handle(&webview, id);
+}
@@ -0,0 +1,384 @@
+use crate::listener::ListenerOptions;
+use crate::mode::Mode;
+use crate::platform::Platform;
+use crate::shortcut::{
+  KeyboardShortcut, PointerShortcut, SequenceShortcut, Shortcut, ShortcutKind,
+};
+use crate::Flags;
+use serde::de::{self, Deserializer};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Serializable configuration of the plugin, mirroring the [`Builder`](crate::Builder).
+///
+/// # Examples
+/// ```json
+/// {
+///   "flags": ["FIND", "RELOAD"],
+///   "shortcuts": ["Ctrl+J", { "key": "F12" }, { "event": "contextmenu" }],
+///   "allowOrigins": ["tauri://localhost", "http://localhost:*"],
+///   "skipSelectors": [".allow-native-menu"],
+///   "targetPlatform": "macos",
+///   "windows": [{ "label": "docs-*", "mode": "allowList", "allow": ["Ctrl+C"] }]
+/// }
+/// ```
+#[non_exhaustive]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Config {
+  pub flags: Flags,
+  pub shortcuts: Vec<ShortcutConfig>,
+  pub mode: Mode,
+  #[serde(deserialize_with = "deserialize_allow")]
+  pub allow: Vec<KeyboardShortcut>,
+  pub allow_origins: Vec<String>,
+  pub deny_origins: Vec<String>,
+  pub windows: Vec<WindowConfig>,
+  pub emit_blocked: bool,
+  pub dispatch_blocked: bool,
+  pub skip_editable: bool,
+  pub skip_selectors: Vec<String>,
+  pub listener_options: ListenerOptions,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub target_platform: Option<Platform>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub csp_nonce: Option<String>,
+}
+
+/// Configuration read from the `plugins.prevent-default` section of `tauri.conf.json`.
+///
+/// It accepts the same fields as [`Config`], along with optional `debug` and `release` sections.
+/// The fields of the section matching the current build override those at the top level.
+///
+/// Values set in code through the [`Builder`](crate::Builder) take precedence over the file.
+///
+/// # Examples
+/// ```json
+/// {
+///   "plugins": {
+///     "prevent-default": {
+///       "flags": ["FIND", "PRINT", "DOWNLOADS"],
+///       "shortcuts": ["Ctrl+J"],
+///       "allowOrigins": ["tauri://localhost", "http://tauri.localhost"],
+///       "debug": {
+///         "allowOrigins": ["http://localhost:*"]
+///       }
+///     }
+///   }
+/// }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PluginConfig(Config);
+
+impl PluginConfig {
+  /// Configuration for the current build, with the matching profile already applied.
+  pub fn config(&self) -> &Config {
+    &self.0
+  }
+
+  pub fn into_config(self) -> Config {
+    self.0
+  }
+}
+
+impl<'de> Deserialize<'de> for PluginConfig {
+  fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+  where
+    D: Deserializer<'de>,
+  {
+    let Some(mut base) = Option::<Map<String, Value>>::deserialize(deserializer)? else {
+      return Ok(Self::default());
+    };
+
+    let debug = base.remove("debug");
+    let release = base.remove("release");
+    let profile = if cfg!(debug_assertions) {
+      debug
+    } else {
+      release
+    };
+
+    match profile {
+      Some(Value::Object(profile)) => base.extend(profile),
+      Some(Value::Null) | None => {}
+      Some(_) => return Err(de::Error::custom("profile must be an object")),
+    }
+
+    Config::deserialize(Value::Object(base))
+      .map(Self)
+      .map_err(de::Error::custom)
+  }
+}
+
+/// Configuration for the windows and webviews whose label matches the pattern.
+///
+/// See [`Builder::window`](crate::Builder::window).
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WindowConfig {
+  pub label: String,
+  #[serde(default)]
+  pub flags: Flags,
+  #[serde(default)]
+  pub shortcuts: Vec<ShortcutConfig>,
+  #[serde(default)]
+  pub mode: Mode,
+  #[serde(default, deserialize_with = "deserialize_allow")]
+  pub allow: Vec<KeyboardShortcut>,
+}
+
+impl WindowConfig {
+  pub fn new(label: impl AsRef<str>) -> Self {
+    Self {
+      label: label.as_ref().to_owned(),
+      flags: Flags::default(),
+      shortcuts: Vec::new(),
+      mode: Mode::default(),
+      allow: Vec::new(),
+    }
+  }
+}
+
+/// A custom shortcut.
+///
+/// It can be deserialized either from a string, such as `Ctrl+Shift+I`, `pointer:contextmenu`
+/// or `sequence:Ctrl+K Ctrl+S`, or from an object, such as `{ "key": "I", "modifiers": ["ctrlKey"] }`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(untagged)]
+pub enum ShortcutConfig {
+  Keyboard(KeyboardShortcut),
+  Pointer(PointerShortcut),
+  Sequence(SequenceShortcut),
+}
+
+impl ShortcutConfig {
+  pub(crate) fn from_shortcut(shortcut: &dyn Shortcut) -> Self {
+    match shortcut.kind() {
+      ShortcutKind::Keyboard(it) => Self::Keyboard(it.clone()),
+      ShortcutKind::Pointer(it) => Self::Pointer(it.clone()),
+      ShortcutKind::Sequence(it) => Self::Sequence(it.clone()),
+    }
+  }
+
+  pub(crate) fn into_shortcut(self) -> Box<dyn Shortcut> {
+    match self {
+      Self::Keyboard(it) => Box::new(it),
+      Self::Pointer(it) => Box::new(it),
+      Self::Sequence(it) => Box::new(it),
+    }
+  }
+}
+
+impl From<KeyboardShortcut> for ShortcutConfig {
+  fn from(shortcut: KeyboardShortcut) -> Self {
+    Self::Keyboard(shortcut)
+  }
+}
+
+impl From<PointerShortcut> for ShortcutConfig {
+  fn from(shortcut: PointerShortcut) -> Self {
+    Self::Pointer(shortcut)
+  }
+}
+
+impl From<SequenceShortcut> for ShortcutConfig {
+  fn from(shortcut: SequenceShortcut) -> Self {
+    Self::Sequence(shortcut)
+  }
+}
+
+impl<'de> Deserialize<'de> for ShortcutConfig {
+  fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+  where
+    D: Deserializer<'de>,
+  {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Raw {
+      String(String),
+      Keyboard(KeyboardShortcut),
+      Pointer(PointerShortcut),
+      Sequence(SequenceShortcut),
+    }
+
+    match Raw::deserialize(deserializer)? {
+      Raw::String(it) if it.trim_start().starts_with("pointer:") => it
+        .parse()
+        .map(Self::Pointer)
+        .map_err(de::Error::custom),
+      Raw::String(it) if it.trim_start().starts_with("sequence:") => it
+        .parse()
+        .map(Self::Sequence)
+        .map_err(de::Error::custom),
+      Raw::String(it) => it
+        .parse()
+        .map(Self::Keyboard)
+        .map_err(de::Error::custom),
+      Raw::Keyboard(it) => Ok(Self::Keyboard(it)),
+      Raw::Pointer(it) => Ok(Self::Pointer(it)),
+      Raw::Sequence(it) => Ok(Self::Sequence(it)),
+    }
+  }
+}
+
+/// Allowed shortcuts can be written in the same forms as the others, but must be keyboard shortcuts.
+fn deserialize_allow<'de, D>(deserializer: D) -> Result<Vec<KeyboardShortcut>, D::Error>
+where
+  D: Deserializer<'de>,
+{
+  Vec::<ShortcutConfig>::deserialize(deserializer)?
+    .into_iter()
+    .map(|it| match it {
+      ShortcutConfig::Keyboard(it) => Ok(it),
+      ShortcutConfig::Pointer(it) => Err(de::Error::custom(format!("{it} can't be allowed"))),
+      ShortcutConfig::Sequence(it) => Err(de::Error::custom(format!("{it} can't be allowed"))),
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod test {
+  use super::{Config, PluginConfig, ShortcutConfig};
+  use crate::shortcut::{KeyboardShortcut, PointerEvent, PointerShortcut};
+  use crate::{Builder, Flags, Mode, Platform};
+
+  #[test]
+  fn deserialize_config() {
+    let json = r#"{
+      "flags": ["FIND", "RELOAD"],
+      "shortcuts": [
+        "Ctrl+J",
+        "pointer:contextmenu",
+        { "key": "F12" },
+        { "key": "I", "modifiers": ["ctrlKey", "shift"] },
+        { "event": "contextmenu" },
+        "sequence:Ctrl+K Ctrl+S",
+        { "steps": ["Ctrl+K", { "key": "g" }], "timeout": 500 }
+      ],
+      "mode": "allowList",
+      "allow": ["Ctrl+S", { "key": "p", "modifiers": ["ctrlKey", "shiftKey"] }],
+      "allowOrigins": ["tauri://localhost"],
+      "targetPlatform": "macos",
+      "windows": [
+        { "label": "docs-*", "flags": ["RELOAD"], "mode": "allowList", "allow": ["Ctrl+C"] }
+      ]
+    }"#;
+
+    let config = serde_json::from_str::<Config>(json).unwrap();
+    assert_eq!(config.flags, Flags::FIND | Flags::RELOAD);
+    assert_eq!(config.mode, Mode::AllowList);
+    assert_eq!(
+      config.allow,
+      [
+        KeyboardShortcut::with_ctrl("S"),
+        KeyboardShortcut::with_ctrl_shift("p")
+      ]
+    );
+    assert_eq!(config.allow_origins, ["tauri://localhost"]);
+    assert_eq!(config.windows[0].label, "docs-*");
+    assert_eq!(config.windows[0].flags, Flags::RELOAD);
+    assert_eq!(config.windows[0].mode, Mode::AllowList);
+    assert_eq!(config.windows[0].allow, [KeyboardShortcut::with_ctrl("C")]);
+    assert_eq!(config.target_platform, Some(Platform::MacOs));
+
+    let shortcuts = config
+      .shortcuts
+      .iter()
+      .map(|it| it.clone().into_shortcut().to_string())
+      .collect::<Vec<_>>();
+
+    assert_eq!(
+      shortcuts,
+      [
+        "keyboard:ctrl+j",
+        "pointer:contextmenu",
+        "keyboard:f12",
+        "keyboard:ctrl+shift+i",
+        "pointer:contextmenu",
+        "sequence:ctrl+k ctrl+s",
+        "sequence:ctrl+k g",
+      ]
+    );
+  }
+
+  #[test]
+  fn invalid_config() {
+    assert!(serde_json::from_str::<Config>(r#"{ "flags": ["UNKNOWN"] }"#).is_err());
+    assert!(serde_json::from_str::<Config>(r#"{ "shortcuts": ["Hyper+J"] }"#).is_err());
+    assert!(serde_json::from_str::<Config>(r#"{ "shortcuts": ["pointer:click"] }"#).is_err());
+    assert!(serde_json::from_str::<Config>(r#"{ "allow": ["pointer:contextmenu"] }"#).is_err());
+  }
+
+  #[test]
+  fn config_round_trip() {
+    let builder = Builder::new()
+      .with_flags(Flags::PRINT | Flags::CONTEXT_MENU)
+      .shortcut(KeyboardShortcut::with_ctrl_shift("E"))
+      .shortcut(PointerShortcut::new(PointerEvent::ContextMenu))
+      .mode(Mode::AllowList)
+      .allow(KeyboardShortcut::with_ctrl("S"))
+      .allow_origin("HTTP://localhost:1420/")
+      .unwrap()
+      .deny_origin("https://*.example.com")
+      .unwrap()
+      .window(
+        "docs-*",
+        Builder::new()
+          .with_flags(Flags::RELOAD)
+          .mode(Mode::AllowList)
+          .allow(KeyboardShortcut::with_ctrl("C")),
+      )
+      .target_platform(Platform::MacOs)
+      .csp_nonce("abc")
+      .emit_blocked(true);
+
+    let config = builder.to_config();
+    assert_eq!(config.allow_origins, ["http://localhost:1420"]);
+    assert_eq!(
+      config.shortcuts[0],
+      ShortcutConfig::Keyboard(KeyboardShortcut::with_ctrl_shift("E"))
+    );
+
+    let json = serde_json::to_string(&config).unwrap();
+    let deserialized = serde_json::from_str::<Config>(&json).unwrap();
+    assert_eq!(deserialized, config);
+
+    let builder = Builder::from_config(deserialized).unwrap();
+    assert_eq!(builder.to_config(), config);
+  }
+
+  #[test]
+  fn serialize_flags() {
+    let flags = Flags::FIND | Flags::RELOAD;
+    assert_eq!(
+      serde_json::to_string(&flags).unwrap(),
+      r#"["FIND","RELOAD"]"#
+    );
+    assert_eq!(serde_json::to_string(&Flags::empty()).unwrap(), "[]");
+  }
+
+  #[test]
+  fn plugin_config_profile() {
+    let json = r#"{
+      "flags": ["FIND"],
+      "emitBlocked": true,
+      "debug": { "flags": ["RELOAD"] },
+      "release": { "flags": ["PRINT"] }
+    }"#;
+
+    let config = serde_json::from_str::<PluginConfig>(json).unwrap();
+    let expected = if cfg!(debug_assertions) {
+      Flags::RELOAD
+    } else {
+      Flags::PRINT
+    };
+    assert_eq!(config.config().flags, expected);
+    assert!(config.config().emit_blocked);
+
+    let config = serde_json::from_str::<PluginConfig>("null").unwrap();
+    assert_eq!(config, PluginConfig::default());
+
+    assert!(serde_json::from_str::<PluginConfig>(r#"{ "debug": 1, "release": 1 }"#).is_err());
+  }
+}
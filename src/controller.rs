@@ -0,0 +1,587 @@
+use crate::error::Result;
+use crate::event::{BlockedEvent, BLOCKED_EVENT};
+use crate::label::LabelPattern;
+use crate::mode::Mode;
+use crate::script::{self, Script, ScriptOptions};
+use crate::shortcut::{self, KeyboardShortcut, Shortcut};
+use crate::{Flags, PreventDefault};
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Runtime, Webview};
+
+pub(crate) type BlockedHandler<R> = Box<dyn Fn(&AppHandle<R>, BlockedEvent) + Send + Sync>;
+pub(crate) type ShortcutHandler<R> = Box<dyn Fn(&Webview<R>, &KeyboardShortcut) + Send + Sync>;
+
+/// Controls which shortcuts are disabled while the app is running.
+///
+/// Every change is immediately applied to all live webviews
+/// and is also used by the webviews created afterwards.
+/// If the plugin has [window-specific configurations](crate::Builder::window),
+/// the changes are applied to each one of them.
+///
+/// # Examples
+/// ```
+/// use tauri::AppHandle;
+/// use tauri_plugin_prevent_default::{Flags, PreventDefault};
+///
+/// fn open_debug_panel(app: &AppHandle) {
+///   // Allow the user to reload the page while the panel is open.
+///   app.prevent_default().disable(Flags::RELOAD).unwrap();
+/// }
+///
+/// fn close_debug_panel(app: &AppHandle) {
+///   app.prevent_default().enable(Flags::RELOAD).unwrap();
+/// }
+/// ```
+pub struct PreventDefaultController<R: Runtime> {
+  app: AppHandle<R>,
+  options: ScriptOptions,
+  script: Script,
+  manual_injection: bool,
+  on_blocked: Option<BlockedHandler<R>>,
+  emit_blocked: bool,
+  /// Handlers of the shortcuts that trigger an action of the app.
+  /// The script refers to them by their index.
+  handlers: Vec<(KeyboardShortcut, ShortcutHandler<R>)>,
+  state: Mutex<State>,
+  /// Always locked after `state`, if both are needed.
+  webviews: Mutex<HashMap<String, Webview<R>>>,
+}
+
+/// Shortcuts prevented in a given webview.
+#[derive(Default)]
+pub(crate) struct Settings {
+  pub(crate) flags: Flags,
+  pub(crate) shortcuts: Vec<Box<dyn Shortcut>>,
+  pub(crate) mode: Mode,
+  pub(crate) allowed: Vec<KeyboardShortcut>,
+}
+
+impl Settings {
+  fn contains(&self, shortcut: &dyn Shortcut) -> bool {
+    self
+      .shortcuts
+      .iter()
+      .any(|it| shortcut::is_same(it.as_ref(), shortcut))
+  }
+}
+
+struct State {
+  default: Settings,
+  windows: Vec<(LabelPattern, Settings)>,
+  /// Number of active suspensions, by the label of the webview or window they apply to.
+  suspensions: HashMap<String, usize>,
+  /// Settings of the suspended webviews, which prevent nothing.
+  suspended: Settings,
+}
+
+impl State {
+  fn new(default: Settings, windows: Vec<(LabelPattern, Settings)>) -> Self {
+    let suspended = Settings {
+      flags: Flags::empty(),
+      ..Settings::default()
+    };
+
+    Self {
+      default,
+      windows,
+      suspensions: HashMap::new(),
+      suspended,
+    }
+  }
+
+  /// Settings for the given webview, falling back to the default ones.
+  fn resolve<R: Runtime>(&self, webview: &Webview<R>) -> &Settings {
+    self.resolve_labels(webview.label(), webview.window_ref().label())
+  }
+
+  fn resolve_labels(&self, webview: &str, window: &str) -> &Settings {
+    if self.suspensions.contains_key(webview) || self.suspensions.contains_key(window) {
+      return &self.suspended;
+    }
+
+    self
+      .windows
+      .iter()
+      .find(|(pattern, _)| pattern.matches(webview) || pattern.matches(window))
+      .map_or(&self.default, |(_, settings)| settings)
+  }
+
+  fn suspend(&mut self, label: &str) {
+    *self
+      .suspensions
+      .entry(label.to_owned())
+      .or_default() += 1;
+  }
+
+  /// Returns `true` if the label is no longer suspended.
+  fn resume(&mut self, label: &str) -> bool {
+    let Some(count) = self.suspensions.get_mut(label) else {
+      return false;
+    };
+
+    *count -= 1;
+    if *count > 0 {
+      return false;
+    }
+
+    self.suspensions.remove(label);
+    true
+  }
+
+  fn settings_mut(&mut self) -> impl Iterator<Item = &mut Settings> {
+    let windows = self.windows.iter_mut().map(|(_, it)| it);
+    std::iter::once(&mut self.default).chain(windows)
+  }
+
+  fn enable(&mut self, flags: Flags) {
+    for settings in self.settings_mut() {
+      settings.flags.insert(flags);
+    }
+  }
+
+  fn disable(&mut self, flags: Flags) {
+    for settings in self.settings_mut() {
+      settings.flags.remove(flags);
+    }
+  }
+
+  /// Returns whether the shortcut was added to any of the settings.
+  fn add_shortcut<S>(&mut self, shortcut: &S) -> bool
+  where
+    S: Shortcut + Clone + 'static,
+  {
+    let mut changed = false;
+    for settings in self.settings_mut() {
+      if !settings.contains(shortcut) {
+        settings
+          .shortcuts
+          .push(Box::new(shortcut.clone()));
+        changed = true;
+      }
+    }
+
+    changed
+  }
+
+  /// Returns whether the shortcut was removed from any of the settings.
+  fn remove_shortcut(&mut self, shortcut: &dyn Shortcut) -> bool {
+    let mut changed = false;
+    for settings in self.settings_mut() {
+      let len = settings.shortcuts.len();
+      settings
+        .shortcuts
+        .retain(|it| !shortcut::is_same(it.as_ref(), shortcut));
+
+      changed |= settings.shortcuts.len() != len;
+    }
+
+    changed
+  }
+}
+
+impl<R: Runtime> PreventDefaultController<R> {
+  #[allow(clippy::too_many_arguments)]
+  pub(crate) fn new(
+    app: AppHandle<R>,
+    default: Settings,
+    windows: Vec<(LabelPattern, Settings)>,
+    options: ScriptOptions,
+    manual_injection: bool,
+    on_blocked: Option<BlockedHandler<R>>,
+    emit_blocked: bool,
+    handlers: Vec<(KeyboardShortcut, ShortcutHandler<R>)>,
+  ) -> Self {
+    let script = script::create(&options, &default, &windows);
+    Self {
+      app,
+      options,
+      script,
+      manual_injection,
+      on_blocked,
+      emit_blocked,
+      handlers,
+      state: Mutex::new(State::new(default, windows)),
+      webviews: Mutex::new(HashMap::new()),
+    }
+  }
+
+  /// Flags whose shortcuts are currently prevented by default.
+  pub fn flags(&self) -> Flags {
+    self.state().default.flags
+  }
+
+  /// Flags whose shortcuts are currently prevented in the webview with the given label.
+  ///
+  /// Returns `None` if there is no such webview.
+  pub fn flags_for(&self, label: &str) -> Option<Flags> {
+    let webview = self.webviews().get(label).cloned()?;
+    Some(self.state().resolve(&webview).flags)
+  }
+
+  /// Start preventing the shortcuts of the given flags, in addition to those already prevented.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the script could not be evaluated in any of the webviews.
+  /// Failures in only some of them are logged instead.
+  pub fn enable(&self, flags: Flags) -> Result<()> {
+    let mut state = self.state();
+    state.enable(flags);
+    self.update(&state)
+  }
+
+  /// Stop preventing the shortcuts of the given flags, letting them reach the webview again.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the script could not be evaluated in any of the webviews.
+  /// Failures in only some of them are logged instead.
+  pub fn disable(&self, flags: Flags) -> Result<()> {
+    let mut state = self.state();
+    state.disable(flags);
+    self.update(&state)
+  }
+
+  /// Disable a custom shortcut.
+  ///
+  /// Does nothing if an identical shortcut has already been added,
+  /// including its options, such as [`skip_editable`](crate::KeyboardShortcutBuilder::skip_editable).
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the script could not be evaluated in any of the webviews.
+  /// Failures in only some of them are logged instead.
+  #[allow(clippy::needless_pass_by_value)]
+  pub fn add_shortcut<S>(&self, shortcut: S) -> Result<()>
+  where
+    S: Shortcut + Clone + 'static,
+  {
+    let mut state = self.state();
+    if state.add_shortcut(&shortcut) {
+      self.update(&state)
+    } else {
+      Ok(())
+    }
+  }
+
+  /// Remove a custom shortcut previously added either through the [`Builder`](crate::Builder)
+  /// or with [`add_shortcut`](Self::add_shortcut).
+  ///
+  /// Only a shortcut with the same options is removed.
+  /// Shortcuts that belong to a flag are not affected. Use [`disable`](Self::disable) for those.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the script could not be evaluated in any of the webviews.
+  /// Failures in only some of them are logged instead.
+  pub fn remove_shortcut(&self, shortcut: &dyn Shortcut) -> Result<()> {
+    let mut state = self.state();
+    if state.remove_shortcut(shortcut) {
+      self.update(&state)
+    } else {
+      Ok(())
+    }
+  }
+
+  /// Stop preventing shortcuts in the webview or window with the given label,
+  /// until the returned guard is dropped.
+  ///
+  /// This also applies to webviews that are reloaded or created while the guard is alive.
+  /// If the same label is suspended more than once, the shortcuts are only prevented again
+  /// once every guard has been dropped.
+  ///
+  /// # Examples
+  /// ```
+  /// use tauri::AppHandle;
+  /// use tauri_plugin_prevent_default::PreventDefault;
+  ///
+  /// fn start_tour(app: &AppHandle) {
+  ///   let _guard = app.prevent_default().suspend("main").unwrap();
+  ///   // Shortcuts behave natively until the end of the scope.
+  /// }
+  /// ```
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the script could not be evaluated in any of the webviews.
+  /// Failures in only some of them are logged instead.
+  /// The suspension is lifted before returning.
+  pub fn suspend(&self, label: &str) -> Result<SuspendGuard<R>> {
+    let result = {
+      let mut state = self.state();
+      state.suspend(label);
+      self.update(&state)
+    };
+
+    let guard = SuspendGuard {
+      app: self.app.clone(),
+      label: label.to_owned(),
+    };
+
+    result.map(|()| guard)
+  }
+
+  /// Stop preventing shortcuts in the webview or window with the given label
+  /// for the given duration.
+  ///
+  /// See [`suspend`](Self::suspend).
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the script could not be evaluated in any of the webviews.
+  /// Failures in only some of them are logged instead.
+  pub fn suspend_for(&self, label: &str, duration: Duration) -> Result<()> {
+    let guard = self.suspend(label)?;
+    tauri::async_runtime::spawn(async move {
+      tokio::time::sleep(duration).await;
+      drop(guard);
+    });
+
+    Ok(())
+  }
+
+  fn resume(&self, label: &str) -> Result<()> {
+    let mut state = self.state();
+    if state.resume(label) {
+      self.update(&state)?;
+    }
+
+    Ok(())
+  }
+
+  /// Script built when the plugin was set up, so that it does not change along with its hash.
+  /// Later changes are applied once the page is loaded.
+  ///
+  /// Returns `None` if the plugin was not [built with manual injection](crate::Builder::build_with_manual_injection).
+  pub(crate) fn script(&self) -> Option<Script> {
+    self.manual_injection.then(|| self.script.clone())
+  }
+
+  /// Script to inject into every webview, unless the plugin was built with manual injection.
+  pub(crate) fn injected_script(&self) -> Option<&Script> {
+    (!self.manual_injection).then_some(&self.script)
+  }
+
+  /// Apply the current state to a webview whose page has just been loaded.
+  pub(crate) fn apply(&self, webview: &Webview<R>) -> Result<()> {
+    let state = self.state();
+    webview.eval(script::update(&self.options, state.resolve(webview)))?;
+    Ok(())
+  }
+
+  /// Notify the listeners that a shortcut has been prevented.
+  pub(crate) fn blocked(&self, app: &AppHandle<R>, event: BlockedEvent) -> Result<()> {
+    if self.emit_blocked {
+      app.emit(BLOCKED_EVENT, event.clone())?;
+    }
+
+    if let Some(on_blocked) = &self.on_blocked {
+      on_blocked(app, event);
+    }
+
+    Ok(())
+  }
+
+  /// Call the handler of a shortcut pressed in the webview, if it has one.
+  pub(crate) fn handle(&self, webview: &Webview<R>, id: usize) {
+    if let Some((shortcut, handler)) = self.handlers.get(id) {
+      handler(webview, shortcut);
+    }
+  }
+
+  pub(crate) fn register_webview(&self, webview: Webview<R>) {
+    let label = webview.label().to_owned();
+    self.webviews().insert(label, webview);
+  }
+
+  pub(crate) fn unregister_window(&self, window_label: &str) {
+    self
+      .webviews()
+      .retain(|_, webview| webview.window_ref().label() != window_label);
+  }
+
+  /// Failures are only reported if no webview could be updated, since webviews closed
+  /// without their window stay registered and would make every later update fail.
+  fn update(&self, state: &State) -> Result<()> {
+    let mut error = None;
+    let mut updated = false;
+    for (label, webview) in self.webviews().iter() {
+      let script = script::update(&self.options, state.resolve(webview));
+      match webview.eval(script) {
+        Ok(()) => updated = true,
+        Err(err) => {
+          log::warn!("failed to update the webview \"{label}\": {err}");
+          error.get_or_insert(err);
+        }
+      }
+    }
+
+    match error {
+      Some(err) if !updated => Err(err.into()),
+      _ => Ok(()),
+    }
+  }
+
+  fn state(&self) -> MutexGuard<'_, State> {
+    self
+      .state
+      .lock()
+      .unwrap_or_else(PoisonError::into_inner)
+  }
+
+  fn webviews(&self) -> MutexGuard<'_, HashMap<String, Webview<R>>> {
+    self
+      .webviews
+      .lock()
+      .unwrap_or_else(PoisonError::into_inner)
+  }
+}
+
+/// Guard returned by [`PreventDefaultController::suspend`].
+///
+/// Shortcuts are prevented again in the suspended webviews when it is dropped.
+#[must_use = "shortcuts are prevented again as soon as the guard is dropped"]
+pub struct SuspendGuard<R: Runtime> {
+  app: AppHandle<R>,
+  label: String,
+}
+
+impl<R: Runtime> SuspendGuard<R> {
+  /// Label of the suspended webview or window.
+  pub fn label(&self) -> &str {
+    &self.label
+  }
+}
+
+impl<R: Runtime> Drop for SuspendGuard<R> {
+  fn drop(&mut self) {
+    let _ = self.app.prevent_default().resume(&self.label);
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::{Settings, State};
+  use crate::label::LabelPattern;
+  use crate::shortcut::{self, KeyboardShortcut, PointerEvent, PointerShortcut};
+  use crate::Flags;
+
+  fn state() -> State {
+    let default = Settings {
+      flags: Flags::FIND | Flags::RELOAD,
+      shortcuts: vec![Box::new(KeyboardShortcut::new("F12"))],
+      ..Settings::default()
+    };
+
+    let docs = Settings {
+      flags: Flags::RELOAD,
+      ..Settings::default()
+    };
+
+    State::new(default, vec![(LabelPattern::new("docs-*"), docs)])
+  }
+
+  fn shortcuts(settings: &Settings) -> Vec<String> {
+    settings
+      .shortcuts
+      .iter()
+      .map(ToString::to_string)
+      .collect()
+  }
+
+  #[test]
+  fn enable_and_disable() {
+    let mut state = state();
+    state.enable(Flags::PRINT);
+    assert_eq!(
+      state.default.flags,
+      Flags::FIND | Flags::RELOAD | Flags::PRINT
+    );
+    assert_eq!(state.windows[0].1.flags, Flags::RELOAD | Flags::PRINT);
+
+    state.disable(Flags::RELOAD | Flags::PRINT);
+    assert_eq!(state.default.flags, Flags::FIND);
+    assert_eq!(state.windows[0].1.flags, Flags::empty());
+  }
+
+  #[test]
+  fn add_and_remove_shortcuts() {
+    let mut state = state();
+    assert!(state.add_shortcut(&KeyboardShortcut::new("F12")));
+    assert_eq!(shortcuts(&state.default), ["keyboard:f12"]);
+    assert_eq!(shortcuts(&state.windows[0].1), ["keyboard:f12"]);
+    assert!(!state.add_shortcut(&KeyboardShortcut::new("F12")));
+
+    assert!(state.add_shortcut(&PointerShortcut::new(PointerEvent::DblClick)));
+    assert!(state.remove_shortcut(&KeyboardShortcut::new("F12")));
+    assert_eq!(shortcuts(&state.default), ["pointer:dblclick"]);
+    assert_eq!(shortcuts(&state.windows[0].1), ["pointer:dblclick"]);
+    assert!(!state.remove_shortcut(&KeyboardShortcut::new("F12")));
+  }
+
+  #[test]
+  fn shortcuts_are_compared_with_their_options() {
+    let mut state = state();
+    let skip_editable = KeyboardShortcut::builder("F12")
+      .skip_editable()
+      .build();
+
+    assert!(state.add_shortcut(&skip_editable));
+    assert_eq!(shortcuts(&state.default), ["keyboard:f12", "keyboard:f12"]);
+
+    assert!(state.remove_shortcut(&KeyboardShortcut::new("F12")));
+    assert!(shortcut::is_same(
+      state.default.shortcuts[0].as_ref(),
+      &skip_editable
+    ));
+    assert!(!state.remove_shortcut(&KeyboardShortcut::new("F12")));
+  }
+
+  #[test]
+  fn modifier_order_is_ignored() {
+    let mut state = state();
+    let shortcut = KeyboardShortcut::builder("I")
+      .shift_key()
+      .ctrl_key()
+      .build();
+
+    assert!(state.add_shortcut(&shortcut));
+    assert!(!state.add_shortcut(&KeyboardShortcut::with_ctrl_shift("I")));
+    assert!(state.remove_shortcut(&KeyboardShortcut::with_ctrl_shift("I")));
+    assert_eq!(shortcuts(&state.default), ["keyboard:f12"]);
+  }
+
+  #[test]
+  fn suspensions_are_counted() {
+    let mut state = state();
+    state.suspend("main");
+    state.suspend("main");
+    assert!(!state.resume("main"));
+    assert_eq!(state.resolve_labels("main", "main").flags, Flags::empty());
+
+    assert!(state.resume("main"));
+    assert_eq!(
+      state.resolve_labels("main", "main").flags,
+      state.default.flags
+    );
+    assert!(!state.resume("main"));
+  }
+
+  #[test]
+  fn suspension_by_webview_or_window_label() {
+    let mut state = state();
+    state.suspend("docs-1");
+    assert_eq!(state.resolve_labels("docs-1", "main").flags, Flags::empty());
+    assert_eq!(
+      state.resolve_labels("child", "docs-1").flags,
+      Flags::empty()
+    );
+    assert_eq!(state.resolve_labels("docs-2", "main").flags, Flags::RELOAD);
+    assert_eq!(
+      state.resolve_labels("child", "main").flags,
+      state.default.flags
+    );
+  }
+}
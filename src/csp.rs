@@ -0,0 +1,87 @@
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use sha2::{Digest, Sha256, Sha384, Sha512};
+use std::collections::HashMap;
+use tauri::utils::config::{Csp, CspDirectiveSources};
+
+/// Algorithm used to create the hash of a [`Script`](crate::Script) for a Content-Security-Policy.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum HashAlgorithm {
+  #[default]
+  Sha256,
+  Sha384,
+  Sha512,
+}
+
+/// Create a source expression such as `'sha256-…'`.
+pub(crate) fn hash(content: &str, algorithm: HashAlgorithm) -> String {
+  let (prefix, digest) = match algorithm {
+    HashAlgorithm::Sha256 => ("sha256", Sha256::digest(content).to_vec()),
+    HashAlgorithm::Sha384 => ("sha384", Sha384::digest(content).to_vec()),
+    HashAlgorithm::Sha512 => ("sha512", Sha512::digest(content).to_vec()),
+  };
+
+  format!("'{prefix}-{}'", STANDARD.encode(digest))
+}
+
+/// Add the sources to the `script-src` directive of the policy.
+///
+/// If the policy has no `script-src` directive, the sources of `default-src` are copied first,
+/// as they would otherwise no longer apply to scripts.
+pub(crate) fn add_script_sources(csp: &mut Csp, sources: &[String]) {
+  let mut directives = HashMap::<String, CspDirectiveSources>::from(csp.clone());
+  if !directives.contains_key("script-src") {
+    let default = directives
+      .get("default-src")
+      .cloned()
+      .unwrap_or_default();
+
+    directives.insert(String::from("script-src"), default);
+  }
+
+  if let Some(script_src) = directives.get_mut("script-src") {
+    for source in sources {
+      if !script_src.contains(source) {
+        script_src.push(source);
+      }
+    }
+  }
+
+  *csp = Csp::DirectiveMap(directives);
+}
+
+#[cfg(test)]
+mod test {
+  use super::HashAlgorithm;
+  use std::collections::HashMap;
+  use tauri::utils::config::{Csp, CspDirectiveSources};
+
+  #[test]
+  fn hash() {
+    let content = "alert('Hello, world.');";
+    assert_eq!(
+      super::hash(content, HashAlgorithm::Sha256),
+      "'sha256-qznLcsROx4GACP2dm0UCKCzCG+HiZ1guq6ZZDob/Tng='"
+    );
+
+    assert!(super::hash(content, HashAlgorithm::Sha384).starts_with("'sha384-"));
+    assert!(super::hash(content, HashAlgorithm::Sha512).starts_with("'sha512-"));
+  }
+
+  #[test]
+  fn add_script_sources() {
+    let sources = |csp: Csp| {
+      let mut directives = HashMap::<String, CspDirectiveSources>::from(csp);
+      let script_src = directives.remove("script-src").unwrap();
+      Vec::<String>::from(script_src)
+    };
+
+    let mut csp = Csp::Policy(String::from("default-src 'self'; img-src 'self' asset:"));
+    super::add_script_sources(&mut csp, &[String::from("'sha256-abc'")]);
+    assert_eq!(sources(csp.clone()), ["'self'", "'sha256-abc'"]);
+
+    super::add_script_sources(&mut csp, &[String::from("'sha256-abc'")]);
+    assert_eq!(sources(csp), ["'self'", "'sha256-abc'"]);
+  }
+}
@@ -1,21 +1,76 @@
-use crate::shortcut::{ModifierKey, PointerEvent};
+use crate::shortcut::{Key, KeyboardShortcut, ModifierKey, PointerEvent};
 use itertools::Itertools;
 
 pub(crate) fn keyboard(key: &str, modifiers: &[ModifierKey]) -> String {
+  keyboard_optional(key, modifiers, &[])
+}
+
+/// Modifiers that may or may not be pressed are followed by `?`, as in `keyboard:ctrl+shift?+f5`.
+/// If none is required and all of them are optional, they are rendered as `*`, as in `keyboard:*+f5`.
+pub(crate) fn keyboard_optional(
+  key: &str,
+  modifiers: &[ModifierKey],
+  optional: &[ModifierKey],
+) -> String {
+  use ModifierKey::{AltKey, CtrlKey, MetaKey, ShiftKey};
+
   let mut buf = String::from("keyboard:");
-  let mod_len = modifiers.len().saturating_mul(6);
+  let mod_len = modifiers
+    .len()
+    .saturating_add(optional.len())
+    .saturating_mul(7);
+
   buf.reserve(key.len().saturating_add(mod_len));
 
-  for modifier in modifiers.iter().unique().sorted() {
-    match modifier {
-      ModifierKey::CtrlKey => buf.push_str("ctrl+"),
-      ModifierKey::ShiftKey => buf.push_str("shift+"),
-      ModifierKey::AltKey => buf.push_str("alt+"),
-      ModifierKey::MetaKey => buf.push_str("meta+"),
+  let is_any = modifiers.is_empty()
+    && [CtrlKey, ShiftKey, AltKey, MetaKey]
+      .iter()
+      .all(|it| optional.contains(it));
+
+  if is_any {
+    buf.push_str("*+");
+  } else {
+    let required = modifiers.iter().map(|it| (*it, ""));
+    let optional = optional
+      .iter()
+      .filter(|it| !modifiers.contains(it))
+      .map(|it| (*it, "?"));
+
+    for (modifier, suffix) in required.chain(optional).unique().sorted() {
+      let name = match modifier {
+        ModifierKey::CmdOrCtrl => "cmdorctrl",
+        ModifierKey::CtrlKey => "ctrl",
+        ModifierKey::ShiftKey => "shift",
+        ModifierKey::AltKey => "alt",
+        ModifierKey::MetaKey => "meta",
+      };
+
+      buf.push_str(name);
+      buf.push_str(suffix);
+      buf.push('+');
     }
   }
 
-  buf.push_str(&key.to_lowercase());
+  // Aliases such as `Esc` are displayed with their canonical name.
+  buf.push_str(&Key::from(key).as_str().to_lowercase());
+  buf
+}
+
+/// Shortcuts matched against the physical key are rendered as `keyboard:ctrl+code:keyf`,
+/// or as `keyboard:ctrl+f|code:keyf` if they also match the layout key.
+pub(crate) fn keyboard_code(
+  key: Option<&str>,
+  code: &str,
+  modifiers: &[ModifierKey],
+  optional: &[ModifierKey],
+) -> String {
+  let mut buf = keyboard_optional(key.unwrap_or_default(), modifiers, optional);
+  if key.is_some() {
+    buf.push('|');
+  }
+
+  buf.push_str("code:");
+  buf.push_str(&code.to_lowercase());
   buf
 }
 
@@ -23,11 +78,29 @@ pub(crate) fn pointer(event: PointerEvent) -> String {
   format!("pointer:{event}")
 }
 
+/// Steps are separated by a space, as in `sequence:ctrl+k ctrl+s`.
+pub(crate) fn sequence(steps: &[KeyboardShortcut]) -> String {
+  let steps = steps
+    .iter()
+    .map(|it| {
+      let step = it.to_string();
+      step
+        .strip_prefix("keyboard:")
+        .map(ToOwned::to_owned)
+        .unwrap_or(step)
+    })
+    .join(" ");
+
+  format!("sequence:{steps}")
+}
+
 #[cfg(test)]
 mod test {
   use super::keyboard as k;
+  use super::keyboard_code as c;
+  use super::keyboard_optional as o;
   use super::pointer as p;
-  use crate::shortcut::ModifierKey::{AltKey, CtrlKey, MetaKey, ShiftKey};
+  use crate::shortcut::ModifierKey::{AltKey, CmdOrCtrl, CtrlKey, MetaKey, ShiftKey};
   use crate::shortcut::PointerEvent;
 
   #[test]
@@ -75,8 +148,40 @@ mod test {
     );
   }
 
+  #[test]
+  fn display_keyboard_code() {
+    assert_eq!(c(None, "KeyF", &[CtrlKey], &[]), "keyboard:ctrl+code:keyf");
+    assert_eq!(
+      c(Some("F"), "KeyF", &[CtrlKey], &[]),
+      "keyboard:ctrl+f|code:keyf"
+    );
+    assert_ne!(c(None, "KeyF", &[CtrlKey], &[]), k("F", &[CtrlKey]));
+  }
+
+  #[test]
+  fn display_keyboard_optional() {
+    assert_eq!(o("F5", &[CtrlKey], &[ShiftKey]), "keyboard:ctrl+shift?+f5");
+    assert_eq!(o("F5", &[], &[AltKey, ShiftKey]), "keyboard:shift?+alt?+f5");
+    assert_eq!(
+      o("F5", &[], &[MetaKey, AltKey, ShiftKey, CtrlKey]),
+      "keyboard:*+f5"
+    );
+    assert_eq!(
+      o("F5", &[CtrlKey], &[MetaKey, AltKey, ShiftKey, CtrlKey]),
+      "keyboard:ctrl+shift?+alt?+meta?+f5"
+    );
+  }
+
+  #[test]
+  fn display_cmd_or_ctrl() {
+    assert_eq!(k("P", &[ShiftKey, CmdOrCtrl]), "keyboard:cmdorctrl+shift+p");
+  }
+
   #[test]
   fn display_pointer() {
     assert_eq!(p(PointerEvent::ContextMenu), "pointer:contextmenu");
+    assert_eq!(p(PointerEvent::AuxClick), "pointer:auxclick");
+    assert_eq!(p(PointerEvent::SelectStart), "pointer:selectstart");
+    assert_eq!(p(PointerEvent::MouseBack), "pointer:mouseback");
   }
 }
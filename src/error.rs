@@ -1,10 +1,30 @@
 use serde::{Serialize, Serializer};
 
+pub type Result<T> = std::result::Result<T, Error>;
+
 #[non_exhaustive]
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
+  #[error("invalid origin: {0}")]
+  InvalidOrigin(String),
   #[error(transparent)]
   Strum(#[from] strum::ParseError),
+  #[error("invalid keyboard shortcut \"{shortcut}\": {reason}")]
+  InvalidKeyboardShortcut { shortcut: String, reason: String },
+  #[error("invalid sequence shortcut \"{shortcut}\": {reason}")]
+  InvalidSequenceShortcut { shortcut: String, reason: String },
+  #[error("empty key in shortcut \"{shortcut}\"")]
+  EmptyKey { shortcut: String },
+  #[error("unknown key \"{key}\" in shortcut \"{shortcut}\"")]
+  UnknownKey { shortcut: String, key: String },
+  #[error("unknown code \"{code}\" in shortcut \"{shortcut}\"")]
+  UnknownCode { shortcut: String, code: String },
+  #[error("duplicate shortcut \"{shortcut}\"")]
+  DuplicateShortcut { shortcut: String },
+  #[error("contradictory shortcut \"{shortcut}\": {reason}")]
+  ContradictoryShortcut { shortcut: String, reason: String },
+  #[error("the handler of \"{handler}\" was registered for another runtime")]
+  UnsupportedRuntime { handler: String },
   #[error(transparent)]
   Tauri(#[from] tauri::Error),
 }
@@ -0,0 +1,64 @@
+use serde::Serialize;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Name of the event emitted when a shortcut is prevented.
+///
+/// See [`Builder::emit_blocked`](crate::Builder::emit_blocked).
+pub const BLOCKED_EVENT: &str = "prevent-default://blocked";
+
+/// Information about a prevented shortcut.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockedEvent {
+  shortcut: String,
+  window: String,
+  webview: String,
+  origin: String,
+  timestamp: u64,
+}
+
+impl BlockedEvent {
+  pub(crate) fn new(shortcut: String, window: String, webview: String, origin: String) -> Self {
+    let timestamp = SystemTime::now()
+      .duration_since(UNIX_EPOCH)
+      .map(|it| u64::try_from(it.as_millis()).unwrap_or(u64::MAX))
+      .unwrap_or_default();
+
+    Self {
+      shortcut,
+      window,
+      webview,
+      origin,
+      timestamp,
+    }
+  }
+
+  /// [Display](std::fmt::Display) of the shortcut that was matched,
+  /// e.g. `keyboard:cmdorctrl+p|code:keyp` or `pointer:contextmenu`.
+  ///
+  /// Keys prevented only because of [`Mode::AllowList`](crate::Mode::AllowList) have no shortcut
+  /// of their own, so the keys that were pressed are reported instead, e.g. `keyboard:ctrl+k`.
+  pub fn shortcut(&self) -> &str {
+    &self.shortcut
+  }
+
+  /// Label of the window in which the shortcut was prevented.
+  pub fn window(&self) -> &str {
+    &self.window
+  }
+
+  /// Label of the webview in which the shortcut was prevented.
+  pub fn webview(&self) -> &str {
+    &self.webview
+  }
+
+  /// Location origin of the webview.
+  pub fn origin(&self) -> &str {
+    &self.origin
+  }
+
+  /// Milliseconds since the Unix epoch.
+  pub fn timestamp(&self) -> u64 {
+    self.timestamp
+  }
+}
@@ -0,0 +1,101 @@
+use std::fmt;
+
+/// Pattern matched against window and webview labels.
+///
+/// Supports the `*` (any sequence of characters) and `?` (any single character) wildcards.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct LabelPattern(String);
+
+impl LabelPattern {
+  pub(crate) fn new(pattern: impl AsRef<str>) -> Self {
+    Self(pattern.as_ref().trim().to_owned())
+  }
+
+  pub(crate) fn matches(&self, label: &str) -> bool {
+    let pattern = self.0.chars().collect::<Vec<_>>();
+    let label = label.chars().collect::<Vec<_>>();
+
+    let (mut p, mut l) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while l < label.len() {
+      match pattern.get(p) {
+        Some('*') => {
+          backtrack = Some((p, l));
+          p += 1;
+        }
+        Some(&c) if c == '?' || c == label[l] => {
+          p += 1;
+          l += 1;
+        }
+        _ => match backtrack {
+          Some((star, pos)) => {
+            backtrack = Some((star, pos + 1));
+            p = star + 1;
+            l = pos + 1;
+          }
+          None => return false,
+        },
+      }
+    }
+
+    pattern[p..].iter().all(|c| *c == '*')
+  }
+
+  /// Equivalent JavaScript regular expression literal.
+  pub(crate) fn to_regex(&self) -> String {
+    let mut regex = String::from("/^");
+    for c in self.0.chars() {
+      match c {
+        '*' => regex.push_str(".*"),
+        '?' => regex.push('.'),
+        '\\' | '^' | '$' | '.' | '|' | '+' | '(' | ')' | '[' | ']' | '{' | '}' | '/' => {
+          regex.push('\\');
+          regex.push(c);
+        }
+        _ => regex.push(c),
+      }
+    }
+
+    regex.push_str("$/");
+    regex
+  }
+}
+
+impl fmt::Display for LabelPattern {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.0)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::LabelPattern;
+
+  #[test]
+  fn label_pattern_matches() {
+    let exact = LabelPattern::new("main");
+    assert!(exact.matches("main"));
+    assert!(!exact.matches("main-2"));
+
+    let prefix = LabelPattern::new("docs-*");
+    assert!(prefix.matches("docs-"));
+    assert!(prefix.matches("docs-viewer"));
+    assert!(!prefix.matches("editor"));
+
+    let single = LabelPattern::new("editor-?");
+    assert!(single.matches("editor-1"));
+    assert!(!single.matches("editor-10"));
+
+    let middle = LabelPattern::new("*-*-view");
+    assert!(middle.matches("a-b-view"));
+    assert!(middle.matches("a-b-c-view"));
+    assert!(!middle.matches("a-view"));
+  }
+
+  #[test]
+  fn label_pattern_regex() {
+    assert_eq!(LabelPattern::new("docs-*").to_regex(), "/^docs-.*$/");
+    assert_eq!(LabelPattern::new("a/b.c?").to_regex(), r"/^a\/b\.c.$/");
+  }
+}
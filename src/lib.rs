@@ -2,32 +2,68 @@
 #![doc = include_str!("../README.md")]
 #![allow(clippy::format_push_string)]
 
+mod command;
+mod config;
+mod controller;
+mod csp;
 mod display;
 mod error;
+mod event;
+mod label;
+mod listener;
+mod mode;
+mod origin;
+mod plugin;
 mod script;
 mod shortcut;
+mod util;
 
-#[cfg(all(target_os = "windows", feature = "platform-windows"))]
 mod platform;
 
 use bitflags::bitflags;
-use tauri::plugin::{Builder as PluginBuilder, TauriPlugin};
-use tauri::{Manager, Runtime};
+use controller::{BlockedHandler, Settings, ShortcutHandler};
+use label::LabelPattern;
+use origin::{OriginPattern, Origins};
+use script::ScriptOptions;
+use serde::de::{self, Deserializer};
+use serde::ser::{SerializeSeq, Serializer};
+use serde::{Deserialize, Serialize};
+use std::any::Any;
+use tauri::plugin::Builder as PluginBuilder;
+use tauri::webview::PageLoadEvent;
+use tauri::{AppHandle, Manager, RunEvent, Runtime, Webview, WindowEvent};
 
-pub use error::Error;
+pub use config::{Config, PluginConfig, ShortcutConfig, WindowConfig};
+pub use controller::{PreventDefaultController, SuspendGuard};
+pub use csp::HashAlgorithm;
+pub use error::{Error, Result};
+pub use event::{BlockedEvent, BLOCKED_EVENT};
+pub use listener::{ListenerOptions, ListenerTarget, Propagation};
+pub use mode::Mode;
+pub use platform::Platform;
+pub use plugin::PreventDefaultPlugin;
 pub use script::Script;
 pub use shortcut::{
-  KeyboardShortcut, KeyboardShortcutBuilder, ModifierKey, PointerEvent, PointerShortcut,
-  PointerShortcutBuilder, Shortcut, ShortcutKind,
+  Key, KeyRepeat, KeyboardEvent, KeyboardShortcut, KeyboardShortcutBuilder, ModifierKey,
+  PointerEvent, PointerShortcut, PointerShortcutBuilder, SequenceShortcut, SequenceShortcutBuilder,
+  Shortcut, ShortcutKind,
 };
 
 #[cfg(all(target_os = "windows", feature = "platform-windows"))]
 pub use platform::windows::PlatformOptions;
 
 bitflags! {
+  /// Groups of built-in shortcuts.
+  ///
+  /// On Apple platforms, `Ctrl` is replaced by `Cmd` in most of them,
+  /// and a few platform-specific variants are also prevented.
+  /// See [`Builder::target_platform`].
+  ///
+  /// Some flags get in the way of ordinary use of the page, such as text selection or dragging,
+  /// so they are not part of the [default](Flags::default) and must be enabled explicitly.
   #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
   pub struct Flags: u32 {
-      /// Find (`Ctrl+F`, `Ctrl+G`, `Ctrl+Shift+G`, `F3`)
+      /// Find (`Ctrl+F`, `Ctrl+G`, `Ctrl+Shift+G`, `F3` with any modifiers)
       const FIND            = 1 << 0;
       /// Caret browsing (`F7`)
       const CARET_BROWSING  = 1 << 1;
@@ -37,7 +73,7 @@ bitflags! {
       const DOWNLOADS       = 1 << 3;
       /// Focus move (`Shift+Tab`)
       const FOCUS_MOVE      = 1 << 4;
-      /// Reload (`F5`, `Ctrl+F5`, `Shift+F5`, `Ctrl+R`, `Ctrl+Shift+R`)
+      /// Reload (`F5` with any modifiers, `Ctrl+R`, `Ctrl+Shift+R`)
       const RELOAD          = 1 << 5;
       /// Source (`Ctrl+U`)
       const SOURCE          = 1 << 6;
@@ -47,40 +83,321 @@ bitflags! {
       const PRINT           = 1 << 8;
       /// Context menu (mouse right click)
       const CONTEXT_MENU    = 1 << 9;
+      /// Middle click (`auxclick`)
+      const AUX_CLICK       = 1 << 10;
+      /// Double click (`dblclick`)
+      const DOUBLE_CLICK    = 1 << 11;
+      /// Dragging images, links and selections (`dragstart`)
+      const DRAG_START      = 1 << 12;
+      /// Text selection (`selectstart`)
+      const SELECT_START    = 1 << 13;
+      /// Dropping items, such as files, into the page (`dragover`, `drop`)
+      const DROP            = 1 << 14;
+      /// Zoom (`Ctrl+=`, `Ctrl+-`, `Ctrl+0`, `Ctrl` + mouse wheel, pinch)
+      ///
+      /// Not included in [`Flags::keyboard`] nor [`Flags::pointer`], as it covers both.
+      const ZOOM            = 1 << 15;
+      /// History navigation (`Alt+ArrowLeft`, `Alt+ArrowRight`, `Cmd+[` and `Cmd+]` on Apple platforms,
+      /// `BrowserBack`, `BrowserForward`, `Backspace` outside editable elements,
+      /// mouse back and forward buttons)
+      ///
+      /// Not included in [`Flags::keyboard`] nor [`Flags::pointer`], as it covers both.
+      const NAVIGATION      = 1 << 16;
   }
 }
 
 impl Flags {
-  /// All keyboard shortcuts.
+  /// All flags made only of keyboard shortcuts.
+  ///
+  /// `ZOOM` and `NAVIGATION` also prevent pointer events, such as `Ctrl` + mouse wheel
+  /// or the mouse back and forward buttons, so they are part of neither this
+  /// nor [`pointer`](Self::pointer).
   pub fn keyboard() -> Self {
-    Self::all().difference(Self::pointer())
+    Self::all().difference(Self::pointer() | Self::ZOOM | Self::NAVIGATION)
   }
 
-  /// All pointer shortcuts.
+  /// All flags made only of pointer shortcuts.
   pub fn pointer() -> Self {
     Self::CONTEXT_MENU
+      | Self::AUX_CLICK
+      | Self::DOUBLE_CLICK
+      | Self::DRAG_START
+      | Self::SELECT_START
+      | Self::DROP
   }
 
   /// Keep `CONTEXT_MENU`, `DEV_TOOLS`, and `RELOAD` shortcuts enabled when in debug mode.
   pub fn debug() -> Self {
     if cfg!(debug_assertions) {
-      Self::all().difference(Self::CONTEXT_MENU | Self::DEV_TOOLS | Self::RELOAD)
+      Self::default().difference(Self::CONTEXT_MENU | Self::DEV_TOOLS | Self::RELOAD)
     } else {
-      Self::all()
+      Self::default()
     }
   }
 }
 
+impl Flags {
+  /// Shortcuts that should be prevented according to the flags.
+  pub(crate) fn shortcuts(self, platform: Platform) -> Vec<Box<dyn Shortcut>> {
+    let mut shortcuts = Vec::new();
+    self.add_keyboard_shortcuts(&mut shortcuts, platform);
+    self.add_pointer_shortcuts(&mut shortcuts);
+    shortcuts
+  }
+
+  #[allow(clippy::too_many_lines)]
+  fn add_keyboard_shortcuts(self, shortcuts: &mut Vec<Box<dyn Shortcut>>, platform: Platform) {
+    use shortcut::ModifierKey::{AltKey, CmdOrCtrl, MetaKey, ShiftKey};
+
+    macro_rules! on_key {
+      ($modifiers:expr; $($key:expr),+) => {
+        $(
+          let shortcut = KeyboardShortcut::with_modifiers($key, $modifiers);
+          shortcuts.push(Box::new(shortcut));
+        )*
+      };
+      ($($key:expr),+) => {
+        $(
+          let shortcut = KeyboardShortcut::new($key);
+          shortcuts.push(Box::new(shortcut));
+        )*
+      };
+    }
+
+    macro_rules! on_any {
+      ($($key:expr),+) => {
+        $(
+          let shortcut = KeyboardShortcut::any_modifiers($key);
+          shortcuts.push(Box::new(shortcut));
+        )*
+      };
+    }
+
+    // Also matched by their physical key, so that they work regardless of the layout.
+    macro_rules! on_key_code {
+      ($modifiers:expr; $($key:expr => $code:literal),+) => {
+        $(
+          let shortcut = KeyboardShortcut::builder($key)
+            .code($code)
+            .modifiers($modifiers)
+            .build();
+
+          shortcuts.push(Box::new(shortcut));
+        )*
+      };
+    }
+
+    macro_rules! on_code {
+      ($modifiers:expr; $($code:literal),+) => {
+        $(
+          let shortcut = KeyboardShortcutBuilder::from_code($code)
+            .modifiers($modifiers)
+            .build();
+
+          shortcuts.push(Box::new(shortcut));
+        )*
+      };
+    }
+
+    let is_apple = platform.is_apple();
+
+    if self.contains(Flags::FIND) {
+      on_any!(Key::F3);
+      on_key_code!(&[CmdOrCtrl]; Key::F => "KeyF", Key::G => "KeyG");
+      on_key_code!(&[CmdOrCtrl, ShiftKey]; Key::G => "KeyG");
+    }
+
+    if self.contains(Flags::CARET_BROWSING) {
+      on_key!(Key::F7);
+    }
+
+    if self.contains(Flags::DEV_TOOLS) {
+      on_key_code!(&[CmdOrCtrl, ShiftKey]; Key::I => "KeyI");
+      if is_apple {
+        // `Option` changes the key on Apple platforms.
+        on_code!(&[MetaKey, AltKey]; "KeyI");
+      }
+    }
+
+    if self.contains(Flags::DOWNLOADS) {
+      on_key_code!(&[CmdOrCtrl]; Key::J => "KeyJ");
+      if is_apple {
+        on_code!(&[MetaKey, AltKey]; "KeyL");
+      }
+    }
+
+    if self.contains(Flags::FOCUS_MOVE) {
+      on_key!(&[ShiftKey]; Key::Tab);
+    }
+
+    if self.contains(Flags::RELOAD) {
+      on_any!(Key::F5);
+      on_key_code!(&[CmdOrCtrl]; Key::R => "KeyR");
+      on_key_code!(&[CmdOrCtrl, ShiftKey]; Key::R => "KeyR");
+    }
+
+    if self.contains(Flags::SOURCE) {
+      on_key_code!(&[CmdOrCtrl]; Key::U => "KeyU");
+      if is_apple {
+        on_code!(&[MetaKey, AltKey]; "KeyU");
+      }
+    }
+
+    if self.contains(Flags::OPEN) {
+      on_key_code!(&[CmdOrCtrl]; Key::O => "KeyO");
+    }
+
+    if self.contains(Flags::PRINT) {
+      on_key_code!(&[CmdOrCtrl]; Key::P => "KeyP");
+      on_key_code!(&[CmdOrCtrl, ShiftKey]; Key::P => "KeyP");
+    }
+
+    if self.contains(Flags::ZOOM) {
+      on_key!(&[CmdOrCtrl]; Key::Equal, Key::Plus, Key::Minus, Key::Digit0);
+      on_key!(&[CmdOrCtrl, ShiftKey]; Key::Plus, Key::Underscore);
+      on_code!(&[CmdOrCtrl]; "Equal", "Minus", "Digit0");
+      on_code!(&[CmdOrCtrl]; "NumpadAdd", "NumpadSubtract", "Numpad0");
+      on_code!(&[CmdOrCtrl, ShiftKey]; "Equal");
+    }
+
+    if self.contains(Flags::NAVIGATION) {
+      // On Apple platforms, `Option+Arrow` moves the caret by word instead.
+      if is_apple {
+        on_key_code!(
+          &[MetaKey];
+          Key::BracketLeft => "BracketLeft",
+          Key::BracketRight => "BracketRight"
+        );
+      } else {
+        on_key!(&[AltKey]; Key::ArrowLeft, Key::ArrowRight);
+      }
+
+      on_key!(Key::BrowserBack, Key::BrowserForward);
+
+      let shortcut = KeyboardShortcut::builder(Key::Backspace)
+        .skip_editable()
+        .build();
+
+      shortcuts.push(Box::new(shortcut));
+    }
+  }
+
+  fn add_pointer_shortcuts(self, shortcuts: &mut Vec<Box<dyn Shortcut>>) {
+    use shortcut::PointerEvent::{
+      AuxClick, ContextMenu, DblClick, DragOver, DragStart, Drop, MouseBack, MouseForward,
+      SelectStart, ZoomGesture, ZoomWheel,
+    };
+
+    macro_rules! on_pointer {
+      ($($event:expr),+) => {
+        $(
+          let shortcut = PointerShortcut::new($event);
+          shortcuts.push(Box::new(shortcut));
+        )*
+      };
+    }
+
+    if self.contains(Flags::CONTEXT_MENU) {
+      on_pointer!(ContextMenu);
+    }
+
+    if self.contains(Flags::AUX_CLICK) {
+      on_pointer!(AuxClick);
+    }
+
+    if self.contains(Flags::DOUBLE_CLICK) {
+      on_pointer!(DblClick);
+    }
+
+    if self.contains(Flags::DRAG_START) {
+      on_pointer!(DragStart);
+    }
+
+    if self.contains(Flags::SELECT_START) {
+      on_pointer!(SelectStart);
+    }
+
+    if self.contains(Flags::DROP) {
+      on_pointer!(DragOver, Drop);
+    }
+
+    if self.contains(Flags::ZOOM) {
+      on_pointer!(ZoomWheel, ZoomGesture);
+    }
+
+    if self.contains(Flags::NAVIGATION) {
+      on_pointer!(MouseBack, MouseForward);
+    }
+  }
+}
+
+/// Every flag except `AUX_CLICK`, `DOUBLE_CLICK`, `DRAG_START`, `SELECT_START`, `DROP`,
+/// `ZOOM` and `NAVIGATION`, which must be enabled explicitly.
 impl Default for Flags {
   fn default() -> Self {
-    Self::all()
+    Self::FIND
+      | Self::CARET_BROWSING
+      | Self::DEV_TOOLS
+      | Self::DOWNLOADS
+      | Self::FOCUS_MOVE
+      | Self::RELOAD
+      | Self::SOURCE
+      | Self::OPEN
+      | Self::PRINT
+      | Self::CONTEXT_MENU
+  }
+}
+
+/// Serialized as a list of flag names, e.g. `["FIND", "RELOAD"]`.
+impl Serialize for Flags {
+  fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    let mut seq = serializer.serialize_seq(None)?;
+    for (name, _) in self.iter_names() {
+      seq.serialize_element(name)?;
+    }
+
+    seq.end()
+  }
+}
+
+impl<'de> Deserialize<'de> for Flags {
+  fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+  where
+    D: Deserializer<'de>,
+  {
+    let mut flags = Flags::empty();
+    for name in Vec::<String>::deserialize(deserializer)? {
+      let flag = Flags::from_name(&name.trim().to_uppercase())
+        .ok_or_else(|| de::Error::custom(format!("unknown flag: {name}")))?;
+
+      flags.insert(flag);
+    }
+
+    Ok(flags)
   }
 }
 
 pub struct Builder {
-  flags: Flags,
+  flags: Option<Flags>,
   shortcuts: Vec<Box<dyn Shortcut>>,
-  check_origin: Option<String>,
+  mode: Option<Mode>,
+  allowed: Vec<KeyboardShortcut>,
+  origins: Origins,
+  /// Origins passed to [`Builder::check_origin`] that could not be parsed.
+  invalid_origins: Vec<String>,
+  windows: Vec<(LabelPattern, Builder)>,
+  on_blocked: Option<Box<dyn Any + Send + Sync>>,
+  handlers: Vec<(KeyboardShortcut, Box<dyn Any + Send + Sync>)>,
+  emit_blocked: Option<bool>,
+  dispatch_blocked: Option<bool>,
+  skip_editable: Option<bool>,
+  skip_selectors: Vec<String>,
+  listener_options: Option<ListenerOptions>,
+  target_platform: Option<Platform>,
+  csp_nonce: Option<String>,
 
   #[cfg(all(target_os = "windows", feature = "platform-windows"))]
   platform: PlatformOptions,
@@ -90,9 +407,22 @@ pub struct Builder {
 impl Default for Builder {
   fn default() -> Self {
     Self {
-      flags: Flags::default(),
+      flags: None,
       shortcuts: Vec::new(),
-      check_origin: None,
+      mode: None,
+      allowed: Vec::new(),
+      origins: Origins::default(),
+      invalid_origins: Vec::new(),
+      windows: Vec::new(),
+      on_blocked: None,
+      handlers: Vec::new(),
+      emit_blocked: None,
+      dispatch_blocked: None,
+      skip_editable: None,
+      skip_selectors: Vec::new(),
+      listener_options: None,
+      target_platform: None,
+      csp_nonce: None,
 
       #[cfg(all(target_os = "windows", feature = "platform-windows"))]
       platform: PlatformOptions::default(),
@@ -106,6 +436,107 @@ impl Builder {
     Self::default()
   }
 
+  /// Create a new builder from a [configuration](Config).
+  ///
+  /// # Errors
+  ///
+  /// Returns [`Error::InvalidOrigin`] if any of the origins can't be parsed.
+  pub fn from_config(config: Config) -> Result<Self> {
+    let mut builder = Self::new()
+      .with_flags(config.flags)
+      .mode(config.mode)
+      .emit_blocked(config.emit_blocked)
+      .dispatch_blocked(config.dispatch_blocked)
+      .skip_editable(config.skip_editable)
+      .listener_options(config.listener_options);
+
+    builder.skip_selectors = config.skip_selectors;
+    builder.allowed = config.allow;
+    builder.target_platform = config.target_platform;
+    builder.csp_nonce = config.csp_nonce;
+
+    builder.shortcuts = config
+      .shortcuts
+      .into_iter()
+      .map(ShortcutConfig::into_shortcut)
+      .collect();
+
+    for origin in config.allow_origins {
+      builder = builder.allow_origin(origin)?;
+    }
+
+    for origin in config.deny_origins {
+      builder = builder.deny_origin(origin)?;
+    }
+
+    for window in config.windows {
+      let mut window_builder = Self::new()
+        .with_flags(window.flags)
+        .mode(window.mode);
+
+      window_builder.allowed = window.allow;
+      window_builder.shortcuts = window
+        .shortcuts
+        .into_iter()
+        .map(ShortcutConfig::into_shortcut)
+        .collect();
+
+      builder = builder.window(window.label, window_builder);
+    }
+
+    Ok(builder)
+  }
+
+  /// Create a [configuration](Config) reflecting the current state of the builder.
+  ///
+  /// Callbacks, such as the one set with [`on_blocked`](Self::on_blocked), are not included.
+  pub fn to_config(&self) -> Config {
+    let shortcuts = |builder: &Self| {
+      builder
+        .shortcuts
+        .iter()
+        .map(|it| ShortcutConfig::from_shortcut(it.as_ref()))
+        .collect()
+    };
+
+    Config {
+      flags: self.flags.unwrap_or_default(),
+      shortcuts: shortcuts(self),
+      mode: self.mode.unwrap_or_default(),
+      allow: self.allowed.clone(),
+      allow_origins: self
+        .origins
+        .allow
+        .iter()
+        .map(ToString::to_string)
+        .collect(),
+      deny_origins: self
+        .origins
+        .deny
+        .iter()
+        .map(ToString::to_string)
+        .collect(),
+      windows: self
+        .windows
+        .iter()
+        .map(|(pattern, builder)| WindowConfig {
+          label: pattern.to_string(),
+          flags: builder.flags.unwrap_or_default(),
+          shortcuts: shortcuts(builder),
+          mode: builder.mode.unwrap_or_default(),
+          allow: builder.allowed.clone(),
+        })
+        .collect(),
+      emit_blocked: self.emit_blocked.unwrap_or_default(),
+      dispatch_blocked: self.dispatch_blocked.unwrap_or_default(),
+      skip_editable: self.skip_editable.unwrap_or_default(),
+      skip_selectors: self.skip_selectors.clone(),
+      listener_options: self.listener_options.unwrap_or_default(),
+      target_platform: self.target_platform,
+      csp_nonce: self.csp_nonce.clone(),
+    }
+  }
+
   /// Set flags to control which shortcuts the plugin should disable.
   ///
   /// # Examples
@@ -118,7 +549,7 @@ impl Builder {
   /// ```
   #[must_use]
   pub fn with_flags(mut self, flags: Flags) -> Self {
-    self.flags = flags;
+    self.flags = Some(flags);
     self
   }
 
@@ -144,17 +575,316 @@ impl Builder {
     self
   }
 
+  /// Disable a custom keyboard shortcut described by a string, such as `Ctrl+Shift+I`.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`Error::InvalidKeyboardShortcut`] if the shortcut can't be parsed.
+  ///
+  /// # Examples
+  /// ```
+  /// # fn main() -> tauri_plugin_prevent_default::Result<()> {
+  /// tauri_plugin_prevent_default::Builder::new()
+  ///   .shortcut_str("Ctrl+J")?
+  ///   .shortcut_str("Shift+F5")?
+  ///   .build();
+  /// # Ok(())
+  /// # }
+  /// ```
+  pub fn shortcut_str(self, shortcut: impl AsRef<str>) -> Result<Self> {
+    let shortcut = shortcut.as_ref().parse::<KeyboardShortcut>()?;
+    Ok(self.shortcut(shortcut))
+  }
+
+  /// Choose how the plugin decides which keyboard shortcuts to prevent.
+  ///
+  /// In [`Mode::AllowList`], every key combination with `Ctrl`, `Alt` or `Meta`,
+  /// along with the function keys, is prevented unless it was [allowed](Self::allow).
+  /// Flags and custom shortcuts are still prevented as usual.
+  ///
+  /// # Examples
+  /// ```
+  /// use tauri_plugin_prevent_default::{KeyboardShortcut, Mode};
+  ///
+  /// tauri_plugin_prevent_default::Builder::new()
+  ///   .mode(Mode::AllowList)
+  ///   .allow(KeyboardShortcut::with_ctrl("s"))
+  ///   .allow(KeyboardShortcut::with_ctrl_shift("p"))
+  ///   .build();
+  /// ```
+  #[must_use]
+  pub fn mode(mut self, mode: Mode) -> Self {
+    self.mode = Some(mode);
+    self
+  }
+
+  /// Keep a keyboard shortcut working in [`Mode::AllowList`].
+  ///
+  /// Allowed shortcuts take precedence over flags and custom shortcuts.
+  /// They have no effect in [`Mode::DenyList`].
+  #[must_use]
+  pub fn allow(mut self, shortcut: KeyboardShortcut) -> Self {
+    self.allowed.push(shortcut);
+    self
+  }
+
   /// Check location origin before disabling the shortcuts.
+  ///
+  /// This is the same as [`allow_origin`](Self::allow_origin), but an invalid origin
+  /// is only reported by [`try_build`](Self::try_build), or makes the setup fail otherwise.
   #[must_use]
   pub fn check_origin(mut self, origin: impl AsRef<str>) -> Self {
     let origin = origin.as_ref().trim();
     if !origin.is_empty() {
-      self.check_origin = Some(origin.to_owned());
+      match OriginPattern::parse(origin) {
+        Ok(origin) => self.origins.allow.push(origin),
+        Err(_) => self.invalid_origins.push(origin.to_owned()),
+      }
     }
 
     self
   }
 
+  /// Disable the shortcuts only if the location origin matches one of the allowed origins.
+  ///
+  /// The origin is normalized before being compared, so that differences in case,
+  /// trailing slashes and default ports are ignored. Both its host and port may contain
+  /// the `*` wildcard. If no origin is allowed, any origin that is not denied is accepted.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`Error::InvalidOrigin`] if the origin can't be parsed.
+  ///
+  /// # Examples
+  /// ```
+  /// # fn main() -> tauri_plugin_prevent_default::Result<()> {
+  /// tauri_plugin_prevent_default::Builder::new()
+  ///   .allow_origin("tauri://localhost")?
+  ///   .allow_origin("http://tauri.localhost")?
+  ///   .allow_origin("http://localhost:*")?
+  ///   .build();
+  /// # Ok(())
+  /// # }
+  /// ```
+  pub fn allow_origin(mut self, origin: impl AsRef<str>) -> Result<Self> {
+    let origin = OriginPattern::parse(origin)?;
+    self.origins.allow.push(origin);
+    Ok(self)
+  }
+
+  /// Never disable the shortcuts if the location origin matches the denied origin,
+  /// even if it is also [allowed](Self::allow_origin).
+  ///
+  /// # Errors
+  ///
+  /// Returns [`Error::InvalidOrigin`] if the origin can't be parsed.
+  pub fn deny_origin(mut self, origin: impl AsRef<str>) -> Result<Self> {
+    let origin = OriginPattern::parse(origin)?;
+    self.origins.deny.push(origin);
+    Ok(self)
+  }
+
+  /// Use a different configuration for the windows and webviews whose label matches the pattern.
+  /// The pattern may contain the `*` and `?` wildcards.
+  ///
+  /// When more than one pattern matches, the first one to be added takes precedence.
+  /// Windows that match none of them use the configuration of this builder.
+  ///
+  /// Only the flags, shortcuts, mode and handlers of the given builder are used.
+  /// The origins and exemptions are always checked according to this builder.
+  ///
+  /// # Examples
+  /// ```
+  /// use tauri_plugin_prevent_default::{Builder, Flags};
+  ///
+  /// Builder::new()
+  ///   .window("editor", Builder::new().with_flags(Flags::all()))
+  ///   .window("docs-*", Builder::new().with_flags(Flags::all().difference(Flags::FIND)))
+  ///   .build();
+  /// ```
+  #[must_use]
+  pub fn window(mut self, label: impl AsRef<str>, mut builder: Builder) -> Self {
+    // Handlers are looked up by their shortcut, regardless of the window.
+    for (shortcut, handler) in std::mem::take(&mut builder.handlers) {
+      add_handler(&mut self.handlers, shortcut, handler);
+    }
+
+    self
+      .windows
+      .push((LabelPattern::new(label), builder));
+
+    self
+  }
+
+  /// Call a function whenever a shortcut is prevented.
+  ///
+  /// This requires the `prevent-default:allow-blocked` permission to be enabled in the capabilities.
+  ///
+  /// # Examples
+  /// ```
+  /// use tauri::AppHandle;
+  ///
+  /// tauri_plugin_prevent_default::Builder::new()
+  ///   .on_blocked(|_app: &AppHandle, event| {
+  ///     if event.shortcut() == "keyboard:cmdorctrl+p|code:keyp" {
+  ///       println!("printing is disabled");
+  ///     }
+  ///   })
+  ///   .build();
+  /// ```
+  #[must_use]
+  pub fn on_blocked<R, F>(mut self, f: F) -> Self
+  where
+    R: Runtime,
+    F: Fn(&AppHandle<R>, BlockedEvent) + Send + Sync + 'static,
+  {
+    let handler: BlockedHandler<R> = Box::new(f);
+    self.on_blocked = Some(Box::new(handler));
+    self
+  }
+
+  /// Disable a keyboard shortcut and call a function whenever it is pressed,
+  /// allowing it to trigger an action of the app instead.
+  ///
+  /// The function receives the webview in which the shortcut was pressed, along with the shortcut.
+  ///
+  /// This requires the `prevent-default:default` permission to be enabled in the capabilities.
+  ///
+  /// # Examples
+  /// ```
+  /// use tauri::Webview;
+  /// use tauri_plugin_prevent_default::KeyboardShortcut;
+  ///
+  /// tauri_plugin_prevent_default::Builder::new()
+  ///   .shortcut_with_handler(KeyboardShortcut::with_ctrl("p"), |webview: &Webview, _| {
+  ///     println!("printing {}", webview.window().label());
+  ///   })
+  ///   .build();
+  /// ```
+  #[must_use]
+  pub fn shortcut_with_handler<R, F>(mut self, shortcut: KeyboardShortcut, f: F) -> Self
+  where
+    R: Runtime,
+    F: Fn(&Webview<R>, &KeyboardShortcut) + Send + Sync + 'static,
+  {
+    let handler: ShortcutHandler<R> = Box::new(f);
+    add_handler(&mut self.handlers, shortcut.clone(), Box::new(handler));
+
+    self.shortcut(shortcut)
+  }
+
+  /// Emit the [`BLOCKED_EVENT`] whenever a shortcut is prevented.
+  ///
+  /// The payload is a [`BlockedEvent`].
+  ///
+  /// This requires the `prevent-default:allow-blocked` permission to be enabled in the capabilities.
+  #[must_use]
+  pub fn emit_blocked(mut self, enabled: bool) -> Self {
+    self.emit_blocked = Some(enabled);
+    self
+  }
+
+  /// Dispatch a `prevent-default:blocked` [`CustomEvent`] on the target of the original event
+  /// whenever a shortcut is prevented, so that the frontend can react without going through Rust.
+  ///
+  /// The event bubbles, and its `detail` holds the `shortcut`, in the same format as
+  /// [`BlockedEvent::shortcut`], the name of the `flag` it belongs to, if any,
+  /// and the original `event`.
+  ///
+  /// [`CustomEvent`]: https://developer.mozilla.org/en-US/docs/Web/API/CustomEvent
+  #[must_use]
+  pub fn dispatch_blocked(mut self, enabled: bool) -> Self {
+    self.dispatch_blocked = Some(enabled);
+    self
+  }
+
+  /// Follow the conventions of another platform when choosing the built-in shortcuts,
+  /// such as whether `Cmd` or `Ctrl` should be used.
+  ///
+  /// By default, this is the [platform the app was compiled for](Platform::current).
+  ///
+  /// # Examples
+  /// ```
+  /// use tauri_plugin_prevent_default::Platform;
+  ///
+  /// tauri_plugin_prevent_default::Builder::new()
+  ///   .target_platform(Platform::MacOs)
+  ///   .build();
+  /// ```
+  #[must_use]
+  pub fn target_platform(mut self, platform: Platform) -> Self {
+    self.target_platform = Some(platform);
+    self
+  }
+
+  /// Do not prevent any shortcut while the focus is in an editable element,
+  /// such as an `<input>`, a `<textarea>` or an element with `contenteditable`.
+  ///
+  /// Elements marked with `data-prevent-default="off"` are always exempt,
+  /// regardless of this option.
+  #[must_use]
+  pub fn skip_editable(mut self, enabled: bool) -> Self {
+    self.skip_editable = Some(enabled);
+    self
+  }
+
+  /// Do not prevent any shortcut on elements matching the CSS selector, or inside them.
+  ///
+  /// The selector is checked against every element in the [composed path] of the event,
+  /// so elements inside an open shadow root are also taken into account.
+  ///
+  /// [composed path]: https://developer.mozilla.org/en-US/docs/Web/API/Event/composedPath
+  ///
+  /// # Examples
+  /// ```
+  /// tauri_plugin_prevent_default::Builder::new()
+  ///   .skip_selector(".allow-native-menu")
+  ///   .skip_selector("[data-editor]")
+  ///   .build();
+  /// ```
+  #[must_use]
+  pub fn skip_selector(mut self, selector: impl AsRef<str>) -> Self {
+    self
+      .skip_selectors
+      .push(selector.as_ref().to_owned());
+
+    self
+  }
+
+  /// Choose how the script listens to the events it prevents.
+  ///
+  /// Individual shortcuts can override this with their own options,
+  /// e.g. [`KeyboardShortcutBuilder::listener_options`].
+  ///
+  /// # Examples
+  /// ```
+  /// use tauri_plugin_prevent_default::{ListenerOptions, ListenerTarget, Propagation};
+  ///
+  /// tauri_plugin_prevent_default::Builder::new()
+  ///   .listener_options(
+  ///     ListenerOptions::new()
+  ///       .target(ListenerTarget::Document)
+  ///       .capture(true)
+  ///       .propagation(Propagation::StopImmediate),
+  ///   )
+  ///   .build();
+  /// ```
+  #[must_use]
+  pub fn listener_options(mut self, options: ListenerOptions) -> Self {
+    self.listener_options = Some(options);
+    self
+  }
+
+  /// Nonce attached to the [script](Self::script), allowing it under a Content-Security-Policy
+  /// when it is injected manually in a `<script nonce="…">` tag.
+  ///
+  /// See [`Script::to_html`] and [`Script::add_to_csp`].
+  #[must_use]
+  pub fn csp_nonce(mut self, nonce: impl AsRef<str>) -> Self {
+    self.csp_nonce = Some(nonce.as_ref().to_owned());
+    self
+  }
+
   /// Windows-specific options.
   #[must_use]
   #[cfg(all(target_os = "windows", feature = "platform-windows"))]
@@ -164,12 +894,11 @@ impl Builder {
   }
 
   /// Build the plugin.
-  pub fn build<R: Runtime>(mut self) -> TauriPlugin<R> {
-    let script = self.create_script();
-    self
-      .plugin_builder()
-      .js_init_script(script.to_string())
-      .build()
+  ///
+  /// The plugin also reads its [configuration](PluginConfig) from `tauri.conf.json`,
+  /// but any value set through the builder takes precedence over the file.
+  pub fn build<R: Runtime>(self) -> PreventDefaultPlugin<R> {
+    PreventDefaultPlugin::new(self.plugin_builder(false, false).build())
   }
 
   /// Build the plugin, but do not inject the script into the webviews.
@@ -188,143 +917,300 @@ impl Builder {
   ///     .unwrap();
   /// }
   /// ```
-  pub fn build_with_manual_injection<R: Runtime>(mut self) -> TauriPlugin<R> {
-    let script = self.create_script();
-    self
-      .plugin_builder()
-      .setup(move |app, _| {
-        app.manage(script);
-        Ok(())
-      })
-      .build()
+  pub fn build_with_manual_injection<R: Runtime>(self) -> PreventDefaultPlugin<R> {
+    PreventDefaultPlugin::new(self.plugin_builder(true, false).build())
   }
 
-  #[allow(clippy::unused_self)]
-  fn plugin_builder<R: Runtime>(self) -> PluginBuilder<R> {
-    #[allow(unused_mut)]
-    let mut builder = PluginBuilder::new("prevent-default");
+  /// Build the plugin, checking the shortcuts for mistakes that would otherwise go unnoticed.
+  ///
+  /// Keys and codes must be values that `KeyboardEvent` can actually have, as listed in the
+  /// [UI Events](https://www.w3.org/TR/uievents-key/) specification,
+  /// and the same shortcut can't be added twice to the same window.
+  ///
+  /// Shortcuts from the configuration file are checked during setup,
+  /// and the app will fail to start if any of them is invalid.
+  ///
+  /// # Errors
+  ///
+  /// - [`Error::InvalidOrigin`] if an origin passed to [`check_origin`](Self::check_origin) is invalid.
+  /// - [`Error::EmptyKey`] if a key or code is empty or whitespace.
+  /// - [`Error::UnknownKey`] or [`Error::UnknownCode`] if a value is not recognized.
+  /// - [`Error::InvalidSequenceShortcut`] if a sequence has less than two steps.
+  /// - [`Error::DuplicateShortcut`] if a shortcut was added more than once, or is already prevented
+  ///   by one of the flags. Shortcuts with a handler can override those of the flags.
+  /// - [`Error::ContradictoryShortcut`] if a shortcut was added again with different options.
+  ///
+  /// # Examples
+  /// ```
+  /// use tauri_plugin_prevent_default::{Error, KeyboardShortcut};
+  ///
+  /// let result = tauri_plugin_prevent_default::Builder::new()
+  ///   .shortcut(KeyboardShortcut::with_ctrl("Contol"))
+  ///   .try_build::<tauri::Wry>();
+  ///
+  /// assert!(matches!(result, Err(Error::UnknownKey { .. })));
+  /// ```
+  pub fn try_build<R: Runtime>(self) -> Result<PreventDefaultPlugin<R>> {
+    self.validate()?;
+    Ok(PreventDefaultPlugin::new(
+      self.plugin_builder(false, true).build(),
+    ))
+  }
 
+  fn plugin_builder<R: Runtime>(
+    self,
+    manual_injection: bool,
+    validate: bool,
+  ) -> PluginBuilder<R, PluginConfig> {
     #[cfg(all(target_os = "windows", feature = "platform-windows"))]
-    {
-      let options = self.platform;
-      builder = builder.on_webview_ready(move |webview| {
+    let options = self.platform.clone();
+
+    PluginBuilder::<R, PluginConfig>::new("prevent-default")
+      .invoke_handler(tauri::generate_handler![command::blocked, command::handle])
+      .setup(move |app, api| {
+        self.check_origins()?;
+        let config = api.config().config().clone();
+        let builder = self.merge(config)?;
+        if validate {
+          builder.validate()?;
+        }
+
+        let controller = builder.into_controller(app.clone(), manual_injection)?;
+
+        app.manage(controller);
+        Ok(())
+      })
+      .on_webview_ready(move |webview| {
+        #[cfg(all(target_os = "windows", feature = "platform-windows"))]
         platform::windows::on_webview_ready(&webview, options.clone());
-      });
-    }
 
-    builder
+        let controller = webview.prevent_default();
+        controller.register_webview(webview.clone());
+      })
+      .on_page_load(|webview, payload| {
+        if payload.event() == PageLoadEvent::Finished {
+          let _ = webview.prevent_default().apply(webview);
+        }
+      })
+      .on_event(|app, event| {
+        if let RunEvent::WindowEvent {
+          label, event: WindowEvent::Destroyed, ..
+        } = event
+        {
+          app.prevent_default().unregister_window(label);
+        }
+      })
   }
 
-  fn create_script(&mut self) -> Script {
-    self.add_keyboard_shortcuts();
-    self.add_pointer_shortcuts();
+  /// Fill in whatever was not set in code with the values from the configuration file.
+  ///
+  /// Shortcuts and window-specific configurations from both sources are kept,
+  /// with those set in code being checked first.
+  fn merge(mut self, config: Config) -> Result<Self> {
+    let mut file = Self::from_config(config)?;
 
-    let mut script = String::new();
+    self.flags = self.flags.or(file.flags);
+    self.mode = self.mode.or(file.mode);
+    self.emit_blocked = self.emit_blocked.or(file.emit_blocked);
+    self.dispatch_blocked = self.dispatch_blocked.or(file.dispatch_blocked);
+    self.skip_editable = self.skip_editable.or(file.skip_editable);
+    self.listener_options = self.listener_options.or(file.listener_options);
+    self.target_platform = self.target_platform.or(file.target_platform);
+    self.csp_nonce = self.csp_nonce.or(file.csp_nonce);
+    self
+      .skip_selectors
+      .append(&mut file.skip_selectors);
+    self.shortcuts.append(&mut file.shortcuts);
+    self.allowed.append(&mut file.allowed);
+    self.windows.append(&mut file.windows);
 
-    for shortcut in &mut self.shortcuts {
-      match shortcut.kind() {
-        ShortcutKind::Keyboard(it) => {
-          let modifiers = it.modifiers();
-          let mut options = String::with_capacity(modifiers.len() * 12);
-          for modifier in modifiers {
-            options.push_str(&format!("{modifier}:true,"));
-          }
+    if self.origins.allow.is_empty() && self.origins.deny.is_empty() {
+      self.origins = file.origins;
+    }
 
-          let options = options.trim_end_matches(',');
-          script.push_str(&format!("onKey('{}',{{{}}});", it.key(), options));
-        }
-        ShortcutKind::Pointer(it) => {
-          script.push_str(&format!("onPointer('{}');", it.event()));
-        }
-      }
+    Ok(self)
+  }
+
+  fn check_origins(&self) -> Result<()> {
+    for origin in &self.invalid_origins {
+      OriginPattern::parse(origin)?;
     }
 
-    let origin = self
-      .check_origin
-      .as_deref()
-      .map(|it| format!("const ORIGIN='{it}';"))
-      .unwrap_or_else(|| "const ORIGIN=null;".to_owned());
+    Ok(())
+  }
+
+  fn validate(&self) -> Result<()> {
+    self.check_origins()?;
+    for builder in std::iter::once(self).chain(self.windows.iter().map(|(_, it)| it)) {
+      shortcut::validate(&builder.shortcuts)?;
+      self.check_flags(builder)?;
+      for allowed in &builder.allowed {
+        allowed.validate()?;
+      }
+    }
 
-    include_str!("../assets/script.js")
-      .trim()
-      .replace("/*ORIGIN*/", &origin)
-      .replace("/*SCRIPT*/", &script)
-      .into()
+    Ok(())
   }
 
-  fn add_keyboard_shortcuts(&mut self) {
-    use shortcut::ModifierKey::{CtrlKey, ShiftKey};
+  /// Custom shortcuts that the flags of the builder already prevent are reported as duplicates,
+  /// unless they have a handler, which takes precedence over the flags.
+  fn check_flags(&self, builder: &Self) -> Result<()> {
+    let platform = self.target_platform.unwrap_or_default();
+    let flags = builder
+      .flags
+      .unwrap_or_default()
+      .shortcuts(platform);
 
-    macro_rules! on_key {
-      ($($arg:literal)+) => {
-        $(
-          let shortcut = KeyboardShortcut::new($arg);
-          self.shortcuts.push(Box::new(shortcut));
-        )*
-      };
-      ($modifiers:expr, $($arg:literal),+) => {
-        $(
-          let shortcut = KeyboardShortcut::with_modifiers($arg, $modifiers);
-          self.shortcuts.push(Box::new(shortcut));
-        )*
-      };
-    }
+    for shortcut in &builder.shortcuts {
+      let duplicate = flags
+        .iter()
+        .any(|flag| match (shortcut.kind(), flag.kind()) {
+          (ShortcutKind::Keyboard(it), ShortcutKind::Keyboard(flag)) => {
+            !self
+              .handlers
+              .iter()
+              .any(|(handled, _)| shortcut::is_same(handled, it))
+              && script::overlaps(it, flag, platform)
+          }
+          (ShortcutKind::Pointer(it), ShortcutKind::Pointer(flag)) => it.event() == flag.event(),
+          _ => false,
+        });
 
-    if self.flags.contains(Flags::FIND) {
-      on_key!("F3");
-      on_key!(&[CtrlKey], "f", "g");
-      on_key!(&[CtrlKey, ShiftKey], "g");
+      if duplicate {
+        return Err(Error::DuplicateShortcut { shortcut: shortcut.to_string() });
+      }
     }
 
-    if self.flags.contains(Flags::CARET_BROWSING) {
-      on_key!("F7");
-    }
+    Ok(())
+  }
 
-    if self.flags.contains(Flags::DEV_TOOLS) {
-      on_key!(&[CtrlKey, ShiftKey], "i");
-    }
+  fn into_controller<R: Runtime>(
+    self,
+    app: AppHandle<R>,
+    manual_injection: bool,
+  ) -> Result<PreventDefaultController<R>> {
+    let windows = self
+      .windows
+      .into_iter()
+      .map(|(pattern, builder)| (pattern, builder.settings()))
+      .collect();
 
-    if self.flags.contains(Flags::DOWNLOADS) {
-      on_key!(&[CtrlKey], "j");
-    }
+    let default = Settings {
+      flags: self.flags.unwrap_or_default(),
+      shortcuts: self.shortcuts,
+      mode: self.mode.unwrap_or_default(),
+      allowed: self.allowed,
+    };
 
-    if self.flags.contains(Flags::FOCUS_MOVE) {
-      on_key!(&[ShiftKey], "Tab");
-    }
+    // The downcast can only fail if the handler was registered for another runtime.
+    let on_blocked = self
+      .on_blocked
+      .map(|it| it.downcast::<BlockedHandler<R>>())
+      .transpose()
+      .map_err(|_| Error::UnsupportedRuntime { handler: String::from("on_blocked") })?
+      .map(|it| *it);
 
-    if self.flags.contains(Flags::RELOAD) {
-      on_key!("F5");
-      on_key!(&[CtrlKey], "F5");
-      on_key!(&[ShiftKey], "F5");
-      on_key!(&[CtrlKey], "r");
-      on_key!(&[CtrlKey, ShiftKey], "r");
-    }
+    let handlers = self
+      .handlers
+      .into_iter()
+      .map(
+        |(shortcut, handler)| match handler.downcast::<ShortcutHandler<R>>() {
+          Ok(handler) => Ok((shortcut, *handler)),
+          Err(_) => Err(Error::UnsupportedRuntime { handler: shortcut.to_string() }),
+        },
+      )
+      .collect::<Result<Vec<_>>>()?;
 
-    if self.flags.contains(Flags::SOURCE) {
-      on_key!(&[CtrlKey], "u");
-    }
+    let emit_blocked = self.emit_blocked.unwrap_or_default();
+    let options = ScriptOptions {
+      origins: self.origins,
+      notify: emit_blocked || on_blocked.is_some(),
+      dispatch: self.dispatch_blocked.unwrap_or_default(),
+      handlers: handlers
+        .iter()
+        .map(|(shortcut, _)| shortcut.clone())
+        .collect(),
+      skip_editable: self.skip_editable.unwrap_or_default(),
+      skip_selectors: self.skip_selectors,
+      listener: self.listener_options.unwrap_or_default(),
+      platform: self.target_platform.unwrap_or_default(),
+      nonce: self.csp_nonce,
+    };
 
-    if self.flags.contains(Flags::OPEN) {
-      on_key!(&[CtrlKey], "o");
-    }
+    Ok(PreventDefaultController::new(
+      app,
+      default,
+      windows,
+      options,
+      manual_injection,
+      on_blocked,
+      emit_blocked,
+      handlers,
+    ))
+  }
 
-    if self.flags.contains(Flags::PRINT) {
-      on_key!(&[CtrlKey], "p");
-      on_key!(&[CtrlKey, ShiftKey], "p");
-    }
+  /// Initialization script reflecting the current state of the builder.
+  ///
+  /// It can be added to the pages with a `<script>` tag, see [`Script::to_html`],
+  /// and its [hash](Script::hash) added to the Content-Security-Policy before the app is built.
+  ///
+  /// The values from the configuration file are only read once the plugin is set up,
+  /// so they are not included.
+  pub fn script(&self) -> Script {
+    let settings = |builder: &Self| Settings {
+      flags: builder.flags.unwrap_or_default(),
+      shortcuts: builder
+        .shortcuts
+        .iter()
+        .map(|it| ShortcutConfig::from_shortcut(it.as_ref()).into_shortcut())
+        .collect(),
+      mode: builder.mode.unwrap_or_default(),
+      allowed: builder.allowed.clone(),
+    };
+
+    let windows: Vec<_> = self
+      .windows
+      .iter()
+      .map(|(pattern, builder)| (pattern.clone(), settings(builder)))
+      .collect();
+
+    let options = ScriptOptions {
+      origins: self.origins.clone(),
+      notify: self.emit_blocked.unwrap_or_default() || self.on_blocked.is_some(),
+      dispatch: self.dispatch_blocked.unwrap_or_default(),
+      handlers: self
+        .handlers
+        .iter()
+        .map(|(shortcut, _)| shortcut.clone())
+        .collect(),
+      skip_editable: self.skip_editable.unwrap_or_default(),
+      skip_selectors: self.skip_selectors.clone(),
+      listener: self.listener_options.unwrap_or_default(),
+      platform: self.target_platform.unwrap_or_default(),
+      nonce: self.csp_nonce.clone(),
+    };
+
+    script::create(&options, &settings(self), &windows)
   }
 
-  fn add_pointer_shortcuts(&mut self) {
-    if self.flags.contains(Flags::CONTEXT_MENU) {
-      let shortcut = PointerShortcut::new(PointerEvent::ContextMenu);
-      self.shortcuts.push(Box::new(shortcut));
+  fn settings(self) -> Settings {
+    Settings {
+      flags: self.flags.unwrap_or_default(),
+      shortcuts: self.shortcuts,
+      mode: self.mode.unwrap_or_default(),
+      allowed: self.allowed,
     }
   }
 }
 
-/// Provide access to the script.
+/// Provide access to the controller and the script.
 pub trait PreventDefault<R: Runtime> {
-  /// Retrieve the script.
+  /// Retrieve the [controller](PreventDefaultController), which allows changing
+  /// the prevented shortcuts while the app is running.
+  fn prevent_default(&self) -> &PreventDefaultController<R>;
+
+  /// Retrieve the script, built from both the code and the configuration file when the plugin
+  /// was set up. It does not change along with the controller.
   ///
   /// # Panics
   ///
@@ -343,50 +1229,196 @@ where
   R: Runtime,
   T: Manager<R>,
 {
+  fn prevent_default(&self) -> &PreventDefaultController<R> {
+    self
+      .app_handle()
+      .state::<PreventDefaultController<R>>()
+      .inner()
+  }
+
   fn prevent_default_script(&self) -> Script {
-    (*self.app_handle().state::<Script>()).clone()
+    self
+      .try_prevent_default_script()
+      .expect("plugin was not built with manual injection")
   }
 
   fn try_prevent_default_script(&self) -> Option<Script> {
     self
-      .app_handle()
-      .try_state::<Script>()
-      .as_deref()
-      .cloned()
+      .try_state::<PreventDefaultController<R>>()
+      .and_then(|it| it.script())
   }
 }
 
 /// Initialize the plugin with default values.
-pub fn init<R: Runtime>() -> TauriPlugin<R> {
+pub fn init<R: Runtime>() -> PreventDefaultPlugin<R> {
   Builder::default().build()
 }
 
 /// Initialize the plugin with default values while also allowing for manual injection.
-pub fn init_with_manual_injection<R: Runtime>() -> TauriPlugin<R> {
+pub fn init_with_manual_injection<R: Runtime>() -> PreventDefaultPlugin<R> {
   Builder::default().build_with_manual_injection()
 }
 
 /// Initialize the plugin with given flags.
-pub fn with_flags<R: Runtime>(flags: Flags) -> TauriPlugin<R> {
+pub fn with_flags<R: Runtime>(flags: Flags) -> PreventDefaultPlugin<R> {
   Builder::new().with_flags(flags).build()
 }
 
 /// Initialize the plugin with given flags while also allowing for manual injection.
-pub fn with_flags_and_manual_injection<R: Runtime>(flags: Flags) -> TauriPlugin<R> {
+pub fn with_flags_and_manual_injection<R: Runtime>(flags: Flags) -> PreventDefaultPlugin<R> {
   Builder::new()
     .with_flags(flags)
     .build_with_manual_injection()
 }
 
+/// A handler replaces the one of the same shortcut, as the script could not tell them apart.
+fn add_handler(
+  handlers: &mut Vec<(KeyboardShortcut, Box<dyn Any + Send + Sync>)>,
+  shortcut: KeyboardShortcut,
+  handler: Box<dyn Any + Send + Sync>,
+) {
+  match handlers
+    .iter_mut()
+    .find(|(it, _)| shortcut::is_same(it, &shortcut))
+  {
+    Some(entry) => entry.1 = handler,
+    None => handlers.push((shortcut, handler)),
+  }
+}
+
 /// Initialize the plugin with the default [debug flags](Flags::debug).
-pub fn debug<R: Runtime>() -> TauriPlugin<R> {
+pub fn debug<R: Runtime>() -> PreventDefaultPlugin<R> {
   Builder::new().with_flags(Flags::debug()).build()
 }
 
 /// Initialize the plugin with the default [debug flags](Flags::debug)
 /// while also allowing for manual injection.
-pub fn debug_with_manual_injection<R: Runtime>() -> TauriPlugin<R> {
+pub fn debug_with_manual_injection<R: Runtime>() -> PreventDefaultPlugin<R> {
   Builder::new()
     .with_flags(Flags::debug())
     .build_with_manual_injection()
 }
+
+#[cfg(test)]
+mod test {
+  use super::{add_handler, Builder, Error, Flags};
+  use crate::platform::Platform;
+  use crate::shortcut::{KeyboardShortcut, PointerEvent, PointerShortcut};
+
+  #[test]
+  fn default_flags() {
+    let expected = Flags::FIND
+      | Flags::CARET_BROWSING
+      | Flags::DEV_TOOLS
+      | Flags::DOWNLOADS
+      | Flags::FOCUS_MOVE
+      | Flags::RELOAD
+      | Flags::SOURCE
+      | Flags::OPEN
+      | Flags::PRINT
+      | Flags::CONTEXT_MENU;
+
+    assert_eq!(Flags::default(), expected);
+  }
+
+  #[test]
+  fn keyboard_and_pointer_flags() {
+    assert!(!Flags::keyboard().contains(Flags::ZOOM));
+    assert!(!Flags::pointer().contains(Flags::ZOOM));
+    assert!(!Flags::keyboard().contains(Flags::NAVIGATION));
+    assert!(!Flags::pointer().contains(Flags::NAVIGATION));
+    assert!(!Flags::default().contains(Flags::NAVIGATION));
+    assert!(Flags::keyboard().contains(Flags::FIND));
+    assert!(Flags::pointer().contains(Flags::CONTEXT_MENU));
+  }
+
+  #[test]
+  fn built_in_shortcuts_match_on_code() {
+    let display = |flags: Flags, platform| {
+      flags
+        .shortcuts(platform)
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+    };
+
+    // Letter keys also match the physical key, so that they work with any layout.
+    for platform in [Platform::MacOs, Platform::Linux] {
+      for display in display(Flags::all(), platform) {
+        let key = display.rsplit(['+', ':']).next().unwrap();
+        let is_letter = key.len() == 1 && key.chars().all(|it| it.is_ascii_alphabetic());
+        assert!(!is_letter, "{display} does not match on code");
+      }
+    }
+
+    assert_eq!(
+      display(Flags::PRINT, Platform::Linux),
+      [
+        "keyboard:cmdorctrl+p|code:keyp",
+        "keyboard:cmdorctrl+shift+p|code:keyp"
+      ]
+    );
+
+    // `Option` changes the key on Apple platforms.
+    let dev_tools = display(Flags::DEV_TOOLS, Platform::MacOs);
+    assert!(dev_tools.contains(&String::from("keyboard:alt+meta+code:keyi")));
+  }
+
+  #[test]
+  fn invalid_checked_origin() {
+    let builder = Builder::new()
+      .check_origin("tauri://localhost")
+      .check_origin(" ");
+    assert!(builder.validate().is_ok());
+    assert_eq!(builder.origins.allow.len(), 1);
+
+    let builder = builder.check_origin("http://localhost/index.html");
+    assert!(matches!(builder.validate(), Err(Error::InvalidOrigin(_))));
+  }
+
+  #[test]
+  fn handlers_of_the_same_shortcut_are_replaced() {
+    let mut handlers = Vec::new();
+    let editable = KeyboardShortcut::builder("P")
+      .ctrl_key()
+      .skip_editable()
+      .build();
+
+    add_handler(&mut handlers, KeyboardShortcut::with_ctrl("P"), Box::new(1));
+    add_handler(&mut handlers, editable, Box::new(2));
+    add_handler(&mut handlers, KeyboardShortcut::with_ctrl("p"), Box::new(3));
+
+    let handlers: Vec<_> = handlers
+      .iter()
+      .map(|(shortcut, it)| {
+        (
+          shortcut.skips_editable(),
+          *it.downcast_ref::<i32>().unwrap(),
+        )
+      })
+      .collect();
+
+    assert_eq!(handlers, [(false, 3), (true, 2)]);
+  }
+
+  #[test]
+  fn shortcuts_already_prevented_by_flags() {
+    let builder = |platform| {
+      Builder::new()
+        .with_flags(Flags::FIND)
+        .target_platform(platform)
+        .shortcut(KeyboardShortcut::with_ctrl("F"))
+    };
+
+    let result = builder(Platform::Linux).validate();
+    assert!(matches!(result, Err(Error::DuplicateShortcut { .. })));
+    assert!(builder(Platform::MacOs).validate().is_ok());
+
+    let result = Builder::new()
+      .with_flags(Flags::CONTEXT_MENU)
+      .shortcut(PointerShortcut::new(PointerEvent::ContextMenu))
+      .validate();
+
+    assert!(matches!(result, Err(Error::DuplicateShortcut { .. })));
+  }
+}
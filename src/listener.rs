@@ -0,0 +1,72 @@
+use serde::{Deserialize, Serialize};
+
+/// How the script listens to the events it prevents.
+///
+/// By default, listeners are attached to `window` during the bubbling phase,
+/// and the events are allowed to keep propagating after being prevented.
+///
+/// # Examples
+/// ```
+/// use tauri_plugin_prevent_default::{ListenerOptions, ListenerTarget, Propagation};
+///
+/// let options = ListenerOptions::new()
+///   .target(ListenerTarget::Document)
+///   .capture(true)
+///   .propagation(Propagation::Stop);
+/// ```
+#[must_use]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ListenerOptions {
+  target: ListenerTarget,
+  capture: bool,
+  propagation: Propagation,
+}
+
+impl ListenerOptions {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Object to which the listeners are attached.
+  pub fn target(mut self, target: ListenerTarget) -> Self {
+    self.target = target;
+    self
+  }
+
+  /// Whether the listeners should run during the capturing phase instead of the bubbling phase.
+  pub fn capture(mut self, enabled: bool) -> Self {
+    self.capture = enabled;
+    self
+  }
+
+  /// What to do with the propagation of an event after it is prevented.
+  pub fn propagation(mut self, propagation: Propagation) -> Self {
+    self.propagation = propagation;
+    self
+  }
+}
+
+/// Object to which the event listeners are attached.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ListenerTarget {
+  #[default]
+  Window,
+  Document,
+}
+
+/// What to do with the propagation of an event after it is prevented.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Propagation {
+  /// Let the event reach the other listeners.
+  #[default]
+  Continue,
+  /// Call `stopPropagation()`.
+  Stop,
+  /// Call `stopImmediatePropagation()`.
+  StopImmediate,
+}
@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+use strum::EnumIs;
+
+/// How the plugin decides which keyboard shortcuts to prevent.
+///
+/// See [`Builder::mode`](crate::Builder::mode).
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Deserialize, Serialize, EnumIs)]
+#[serde(rename_all = "camelCase")]
+pub enum Mode {
+  /// Prevent only the shortcuts of the enabled flags and the custom shortcuts.
+  #[default]
+  DenyList,
+  /// Also prevent every key combination with `Ctrl`, `Alt` or `Meta`, along with the function keys,
+  /// except those explicitly [allowed](crate::Builder::allow).
+  ///
+  /// Keys typed without those modifiers, with `AltGr`, or with `Option` on Apple platforms,
+  /// are left alone.
+  AllowList,
+}
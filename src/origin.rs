@@ -0,0 +1,224 @@
+use crate::error::{Error, Result};
+use std::fmt;
+use tauri::Url;
+
+/// Origins in which the shortcuts should be prevented.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Origins {
+  pub(crate) allow: Vec<OriginPattern>,
+  pub(crate) deny: Vec<OriginPattern>,
+}
+
+/// Normalized origin, possibly containing wildcards in its host or port.
+///
+/// - `tauri://localhost`
+/// - `http://localhost:1420`
+/// - `https://*.example.com`
+/// - `http://localhost:*`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct OriginPattern {
+  scheme: String,
+  host: String,
+  port: Option<String>,
+}
+
+impl OriginPattern {
+  pub(crate) fn parse(origin: impl AsRef<str>) -> Result<Self> {
+    let raw = origin.as_ref();
+    let invalid = || Error::InvalidOrigin(raw.to_owned());
+
+    let origin = raw.trim().trim_end_matches('/').to_lowercase();
+    let (scheme, authority) = origin.split_once("://").ok_or_else(invalid)?;
+
+    let is_valid_scheme = scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+      && scheme
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'));
+
+    if !is_valid_scheme {
+      return Err(invalid());
+    }
+
+    let (host, port) = match authority.rsplit_once(':') {
+      Some((host, port)) if !host.starts_with('[') || host.ends_with(']') => (host, Some(port)),
+      _ => (authority, None),
+    };
+
+    let is_valid_host = if let Some(ipv6) = host.strip_prefix('[') {
+      ipv6.strip_suffix(']').is_some_and(|it| {
+        !it.is_empty()
+          && it
+            .chars()
+            .all(|c| c.is_ascii_hexdigit() || c == ':')
+      })
+    } else {
+      !host.is_empty()
+        && host
+          .chars()
+          .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | '_' | '*'))
+    };
+
+    if !is_valid_host {
+      return Err(invalid());
+    }
+
+    let port = match port {
+      None => None,
+      Some("*") => Some(String::from("*")),
+      Some(port) => {
+        let port = port.parse::<u16>().map_err(|_| invalid())?;
+        (Some(port) != default_port(scheme)).then(|| port.to_string())
+      }
+    };
+
+    Ok(Self {
+      scheme: scheme.to_owned(),
+      host: host.to_owned(),
+      port,
+    })
+  }
+
+  /// Equivalent JavaScript regular expression literal.
+  pub(crate) fn to_regex(&self) -> String {
+    let mut regex = String::from("/^");
+    regex.push_str(&escape(&self.scheme));
+    regex.push_str(":\\/\\/");
+
+    for (i, part) in self.host.split('*').enumerate() {
+      if i > 0 {
+        regex.push_str("[^:\\/]*");
+      }
+
+      regex.push_str(&escape(part));
+    }
+
+    match self.port.as_deref() {
+      Some("*") => regex.push_str("(:\\d+)?"),
+      Some(port) => {
+        regex.push(':');
+        regex.push_str(port);
+      }
+      None => {}
+    }
+
+    regex.push_str("$/");
+    regex
+  }
+}
+
+impl fmt::Display for OriginPattern {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}://{}", self.scheme, self.host)?;
+    if let Some(port) = &self.port {
+      write!(f, ":{port}")?;
+    }
+
+    Ok(())
+  }
+}
+
+/// Origin of the URL, as `window.location.origin` would report it.
+pub(crate) fn of_url(url: &Url) -> String {
+  match url.host_str() {
+    Some(host) => {
+      let mut origin = format!("{}://{host}", url.scheme());
+      if let Some(port) = url.port() {
+        origin.push_str(&format!(":{port}"));
+      }
+
+      origin
+    }
+    None => String::from("null"),
+  }
+}
+
+fn default_port(scheme: &str) -> Option<u16> {
+  match scheme {
+    "http" | "ws" => Some(80),
+    "https" | "wss" => Some(443),
+    _ => None,
+  }
+}
+
+fn escape(value: &str) -> String {
+  let mut buf = String::with_capacity(value.len());
+  for c in value.chars() {
+    if matches!(c, '.' | '+' | '[' | ']' | '/') {
+      buf.push('\\');
+    }
+
+    buf.push(c);
+  }
+
+  buf
+}
+
+#[cfg(test)]
+mod test {
+  use super::OriginPattern;
+
+  fn parse(origin: &str) -> String {
+    OriginPattern::parse(origin).unwrap().to_string()
+  }
+
+  #[test]
+  fn normalize_origin() {
+    assert_eq!(parse("tauri://localhost"), "tauri://localhost");
+    assert_eq!(parse("http://tauri.localhost/"), "http://tauri.localhost");
+    assert_eq!(parse("HTTP://LocalHost:1420"), "http://localhost:1420");
+    assert_eq!(parse("http://localhost:80"), "http://localhost");
+    assert_eq!(parse("https://example.com:443/"), "https://example.com");
+    assert_eq!(parse("https://example.com:80"), "https://example.com:80");
+    assert_eq!(parse("http://*.localhost:*"), "http://*.localhost:*");
+    assert_eq!(parse("http://[::1]:1420"), "http://[::1]:1420");
+  }
+
+  #[test]
+  fn origin_of_url() {
+    let origin = |url: &str| super::of_url(&url.parse().unwrap());
+    assert_eq!(origin("tauri://localhost/index.html"), "tauri://localhost");
+    assert_eq!(origin("http://tauri.localhost/"), "http://tauri.localhost");
+    assert_eq!(
+      origin("http://localhost:1420/#/home"),
+      "http://localhost:1420"
+    );
+    assert_eq!(origin("https://example.com:443/a?b"), "https://example.com");
+    assert_eq!(origin("about:blank"), "null");
+  }
+
+  #[test]
+  fn invalid_origin() {
+    for origin in [
+      "",
+      "localhost",
+      "://localhost",
+      "http://",
+      "http://localhost/index.html",
+      "http://localhost:abc",
+      "http://localhost:65536",
+      "http://local host",
+      "1http://localhost",
+      "http://[::1",
+    ] {
+      assert!(OriginPattern::parse(origin).is_err(), "{origin}");
+    }
+  }
+
+  #[test]
+  fn origin_regex() {
+    let regex = |origin: &str| OriginPattern::parse(origin).unwrap().to_regex();
+    assert_eq!(regex("tauri://localhost"), r"/^tauri:\/\/localhost$/");
+    assert_eq!(
+      regex("http://localhost:1420"),
+      r"/^http:\/\/localhost:1420$/"
+    );
+    assert_eq!(
+      regex("https://*.example.com"),
+      r"/^https:\/\/[^:\/]*\.example\.com$/"
+    );
+    assert_eq!(
+      regex("http://localhost:*"),
+      r"/^http:\/\/localhost(:\d+)?$/"
+    );
+  }
+}
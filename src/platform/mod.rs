@@ -1,2 +1,47 @@
 #[cfg(all(target_os = "windows", feature = "platform-windows"))]
 pub mod windows;
+
+use serde::{Deserialize, Serialize};
+
+/// Platform whose conventions the built-in shortcuts should follow.
+///
+/// By default, this is the platform the app was compiled for.
+/// See [`Builder::target_platform`](crate::Builder::target_platform).
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Platform {
+  Android,
+  Ios,
+  Linux,
+  MacOs,
+  Windows,
+}
+
+impl Platform {
+  /// The platform the app was compiled for.
+  pub fn current() -> Self {
+    if cfg!(target_os = "macos") {
+      Self::MacOs
+    } else if cfg!(target_os = "ios") {
+      Self::Ios
+    } else if cfg!(target_os = "android") {
+      Self::Android
+    } else if cfg!(target_os = "windows") {
+      Self::Windows
+    } else {
+      Self::Linux
+    }
+  }
+
+  /// Whether the platform uses `Cmd` instead of `Ctrl` for most shortcuts.
+  pub fn is_apple(self) -> bool {
+    matches!(self, Self::MacOs | Self::Ios)
+  }
+}
+
+impl Default for Platform {
+  fn default() -> Self {
+    Self::current()
+  }
+}
@@ -125,6 +125,8 @@ impl PlatformOptions {
 
   /// Determines whether the user is able to impact the zoom of the WebView.
   ///
+  /// See also [`Flags::ZOOM`](crate::Flags::ZOOM), which is available on every platform.
+  ///
   /// <https://learn.microsoft.com/en-us/dotnet/api/microsoft.web.webview2.core.corewebview2settings.iszoomcontrolenabled>
   pub fn zoom_control(mut self, enabled: bool) -> Self {
     self.zoom_control = Some(enabled);
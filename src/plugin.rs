@@ -0,0 +1,68 @@
+use crate::config::PluginConfig;
+use crate::PreventDefault;
+use serde_json::Value;
+use std::error::Error;
+use tauri::ipc::Invoke;
+use tauri::plugin::{Plugin, TauriPlugin};
+use tauri::webview::PageLoadPayload;
+use tauri::{AppHandle, RunEvent, Runtime, Url, Webview, Window};
+
+/// The plugin returned by the [`Builder`](crate::Builder).
+///
+/// The configuration file is only available once the plugin is set up,
+/// so the initialization script is only built then, from the merged configuration.
+/// This is not possible with a [`TauriPlugin`], whose script is fixed when it is built.
+pub struct PreventDefaultPlugin<R: Runtime> {
+  inner: TauriPlugin<R, PluginConfig>,
+  script: Option<String>,
+}
+
+impl<R: Runtime> PreventDefaultPlugin<R> {
+  pub(crate) fn new(inner: TauriPlugin<R, PluginConfig>) -> Self {
+    Self { inner, script: None }
+  }
+}
+
+impl<R: Runtime> Plugin<R> for PreventDefaultPlugin<R> {
+  fn name(&self) -> &'static str {
+    self.inner.name()
+  }
+
+  fn initialize(&mut self, app: &AppHandle<R>, config: Value) -> Result<(), Box<dyn Error>> {
+    self.inner.initialize(app, config)?;
+    self.script = app
+      .prevent_default()
+      .injected_script()
+      .map(ToString::to_string);
+
+    Ok(())
+  }
+
+  fn initialization_script(&self) -> Option<String> {
+    self.script.clone()
+  }
+
+  fn window_created(&mut self, window: Window<R>) {
+    self.inner.window_created(window);
+  }
+
+  fn webview_created(&mut self, webview: Webview<R>) {
+    self.inner.webview_created(webview);
+  }
+
+  fn on_navigation(&mut self, webview: &Webview<R>, url: &Url) -> bool {
+    self.inner.on_navigation(webview, url)
+  }
+
+  fn on_page_load(&mut self, webview: &Webview<R>, payload: &PageLoadPayload<'_>) {
+    self.inner.on_page_load(webview, payload);
+  }
+
+  fn on_event(&mut self, app: &AppHandle<R>, event: &RunEvent) {
+    self.inner.on_event(app, event);
+  }
+
+  fn extend_api(&mut self, invoke: Invoke<R>) -> bool {
+    self.inner.extend_api(invoke)
+  }
+}
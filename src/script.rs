@@ -1,29 +1,128 @@
+use crate::controller::Settings;
+use crate::csp::{self, HashAlgorithm};
+use crate::label::LabelPattern;
+use crate::listener::ListenerOptions;
+use crate::mode::Mode;
+use crate::origin::{OriginPattern, Origins};
+use crate::platform::Platform;
+use crate::shortcut::{self, Key, KeyRepeat, KeyboardEvent, KeyboardShortcut, ShortcutKind};
+use itertools::Itertools;
+use serde::Serialize;
 use std::fmt;
 use std::ops::Deref;
 use std::sync::Arc;
+use tauri::utils::config::Csp;
+
+/// Name of the global object through which the script can be updated at runtime.
+const RUNTIME_API: &str = "__TAURI_PLUGIN_PREVENT_DEFAULT__";
 
 /// Script to be injected into the webview.
 #[derive(Clone, Debug)]
-pub struct Script(Arc<str>);
+pub struct Script {
+  content: Arc<str>,
+  nonce: Option<Arc<str>>,
+}
 
 impl Script {
   #[must_use]
   pub fn new(script: impl AsRef<str>) -> Self {
-    Self(Arc::from(script.as_ref()))
+    Self::from(script.as_ref())
   }
 
   #[must_use]
   pub fn join(&self, script: impl AsRef<str>) -> Self {
-    let mut buf = String::from(self.0.as_ref());
+    let mut buf = String::from(self.content.as_ref());
     buf.push('\n');
     buf.push_str(script.as_ref());
-    Self::from(buf)
+
+    let mut joined = Self::from(buf);
+    joined.nonce.clone_from(&self.nonce);
+    joined
+  }
+
+  /// Set the nonce that allows the script under a Content-Security-Policy.
+  ///
+  /// See [`Builder::csp_nonce`](crate::Builder::csp_nonce).
+  #[must_use]
+  pub fn with_nonce(mut self, nonce: impl AsRef<str>) -> Self {
+    self.nonce = Some(Arc::from(nonce.as_ref()));
+    self
+  }
+
+  /// Nonce that allows the script under a Content-Security-Policy, if any.
+  pub fn nonce(&self) -> Option<&str> {
+    self.nonce.as_deref()
+  }
+
+  /// Wrap the script in a `<script>` tag, along with its [nonce](Self::nonce), if any.
+  ///
+  /// # Examples
+  /// ```
+  /// use tauri_plugin_prevent_default::Script;
+  ///
+  /// let script = Script::new("console.log(1);").with_nonce("abc");
+  /// assert_eq!(script.to_html(), r#"<script nonce="abc">console.log(1);</script>"#);
+  /// ```
+  pub fn to_html(&self) -> String {
+    match &self.nonce {
+      Some(nonce) => {
+        let nonce = nonce.replace('&', "&amp;").replace('"', "&quot;");
+
+        format!(r#"<script nonce="{nonce}">{}</script>"#, self.content)
+      }
+      None => format!("<script>{}</script>", self.content),
+    }
+  }
+
+  /// Hash of the script, as a source expression for a Content-Security-Policy, e.g. `'sha256-…'`.
+  ///
+  /// It only matches a `<script>` tag holding exactly this script, such as the one returned by
+  /// [`to_html`](Self::to_html). It does not match the script injected by the plugin,
+  /// which Tauri wraps in a function like any other initialization script.
+  ///
+  /// # Examples
+  /// ```
+  /// use tauri_plugin_prevent_default::{Flags, HashAlgorithm};
+  ///
+  /// let script = tauri_plugin_prevent_default::Builder::new()
+  ///   .with_flags(Flags::PRINT)
+  ///   .script();
+  ///
+  /// let hash = script.hash(HashAlgorithm::Sha256);
+  /// assert!(hash.starts_with("'sha256-"));
+  /// ```
+  pub fn hash(&self, algorithm: HashAlgorithm) -> String {
+    csp::hash(&self.content, algorithm)
+  }
+
+  /// Add the [hash](Self::hash) of the script and its [nonce](Self::nonce), if any,
+  /// to the `script-src` directive of the policy, for a `<script>` tag holding it.
+  ///
+  /// If there is no `script-src` directive, the sources of `default-src` are copied into a new one.
+  ///
+  /// # Examples
+  /// ```no_run
+  /// use tauri_plugin_prevent_default::HashAlgorithm;
+  ///
+  /// let script = tauri_plugin_prevent_default::Builder::new().script();
+  /// let mut context = tauri::generate_context!();
+  /// if let Some(csp) = context.config_mut().app.security.csp.as_mut() {
+  ///   script.add_to_csp(csp, HashAlgorithm::Sha256);
+  /// }
+  /// ```
+  pub fn add_to_csp(&self, csp: &mut Csp, algorithm: HashAlgorithm) {
+    let mut sources = vec![self.hash(algorithm)];
+    if let Some(nonce) = &self.nonce {
+      sources.push(format!("'nonce-{nonce}'"));
+    }
+
+    csp::add_script_sources(csp, &sources);
   }
 }
 
 impl AsRef<str> for Script {
   fn as_ref(&self) -> &str {
-    self.0.as_ref()
+    self.content.as_ref()
   }
 }
 
@@ -31,24 +130,829 @@ impl Deref for Script {
   type Target = str;
 
   fn deref(&self) -> &Self::Target {
-    self.0.as_ref()
+    self.content.as_ref()
   }
 }
 
 impl fmt::Display for Script {
   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-    write!(f, "{}", self.0)
+    write!(f, "{}", self.content)
   }
 }
 
 impl From<&str> for Script {
   fn from(value: &str) -> Self {
-    Script(Arc::from(value))
+    Script {
+      content: Arc::from(value),
+      nonce: None,
+    }
   }
 }
 
 impl From<String> for Script {
   fn from(value: String) -> Self {
-    Script(Arc::from(value))
+    Script {
+      content: Arc::from(value),
+      nonce: None,
+    }
+  }
+}
+
+/// Options that apply to the whole script, regardless of the window.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ScriptOptions {
+  pub(crate) origins: Origins,
+  /// Whether the script should notify the plugin when a shortcut is prevented.
+  pub(crate) notify: bool,
+  /// Whether the script should dispatch a DOM event when a shortcut is prevented.
+  pub(crate) dispatch: bool,
+  /// Shortcuts whose handler should be called when they are prevented.
+  pub(crate) handlers: Vec<KeyboardShortcut>,
+  /// Exemptions that apply to every shortcut.
+  pub(crate) skip_editable: bool,
+  pub(crate) skip_selectors: Vec<String>,
+  /// Listener options used by the shortcuts that do not have their own.
+  pub(crate) listener: ListenerOptions,
+  pub(crate) platform: Platform,
+  pub(crate) nonce: Option<String>,
+}
+
+impl ScriptOptions {
+  fn to_json(&self) -> String {
+    let regexes = |patterns: &[OriginPattern]| {
+      patterns
+        .iter()
+        .map(|it| regex_source(&it.to_regex()))
+        .collect()
+    };
+
+    to_json(&OptionsTable {
+      origins: OriginsTable {
+        allow: regexes(&self.origins.allow),
+        deny: regexes(&self.origins.deny),
+      },
+      notify: self.notify,
+      dispatch: self.dispatch,
+      skip_editable: self.skip_editable,
+      skip_selectors: &self.skip_selectors,
+      listener: self.listener,
+    })
+  }
+}
+
+/// Options in the form read by the script.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct OptionsTable<'a> {
+  origins: OriginsTable,
+  notify: bool,
+  #[serde(skip_serializing_if = "crate::util::is_false")]
+  dispatch: bool,
+  skip_editable: bool,
+  skip_selectors: &'a [String],
+  listener: ListenerOptions,
+}
+
+#[derive(Serialize)]
+struct OriginsTable {
+  allow: Vec<String>,
+  deny: Vec<String>,
+}
+
+/// Shortcuts in the form read by the script, without duplicates.
+#[derive(Serialize)]
+struct ShortcutTable<'a> {
+  keys: Vec<KeyEntry<'a>>,
+  pointers: Vec<PointerEntry<'a>>,
+  #[serde(skip_serializing_if = "Vec::is_empty")]
+  sequences: Vec<SequenceEntry<'a>>,
+  #[serde(skip_serializing_if = "Mode::is_deny_list")]
+  mode: Mode,
+  /// Shortcuts that are never prevented in [`Mode::AllowList`].
+  #[serde(skip_serializing_if = "Vec::is_empty")]
+  allow: Vec<KeyEntry<'a>>,
+}
+
+#[derive(Clone, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct KeyEntry<'a> {
+  key: &'a str,
+  /// Whether the key should be matched against `KeyboardEvent.code`.
+  #[serde(skip_serializing_if = "crate::util::is_false")]
+  code: bool,
+  #[serde(skip_serializing_if = "Vec::is_empty")]
+  modifiers: Vec<String>,
+  #[serde(skip_serializing_if = "Vec::is_empty")]
+  optional: Vec<String>,
+  #[serde(skip_serializing_if = "crate::util::is_false")]
+  skip_editable: bool,
+  #[serde(skip_serializing_if = "<[_]>::is_empty")]
+  skip_selectors: &'a [String],
+  #[serde(skip_serializing_if = "Option::is_none")]
+  listener: Option<ListenerOptions>,
+  #[serde(skip_serializing_if = "crate::shortcut::is_keydown_only")]
+  events: &'a [KeyboardEvent],
+  #[serde(skip_serializing_if = "KeyRepeat::is_any")]
+  repeat: KeyRepeat,
+  /// Index of the handler of the shortcut, if it has one.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  handler: Option<usize>,
+  /// Display of the shortcut, only needed to notify the plugin or to dispatch the DOM event.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  display: Option<String>,
+  /// Name of the flag the shortcut belongs to, only needed to dispatch the DOM event.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  flag: Option<&'static str>,
+}
+
+#[derive(Clone, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PointerEntry<'a> {
+  event: String,
+  #[serde(skip_serializing_if = "crate::util::is_false")]
+  skip_editable: bool,
+  #[serde(skip_serializing_if = "<[_]>::is_empty")]
+  skip_selectors: &'a [String],
+  #[serde(skip_serializing_if = "Option::is_none")]
+  listener: Option<ListenerOptions>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  flag: Option<&'static str>,
+}
+
+#[derive(Clone, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SequenceEntry<'a> {
+  /// Each step is matched by any of its entries, i.e. by its key or by its code.
+  steps: Vec<Vec<KeyEntry<'a>>>,
+  /// Milliseconds.
+  timeout: u128,
+  #[serde(skip_serializing_if = "crate::util::is_false")]
+  prevent_all_steps: bool,
+  display: String,
+  #[serde(skip_serializing_if = "crate::util::is_false")]
+  skip_editable: bool,
+  #[serde(skip_serializing_if = "<[_]>::is_empty")]
+  skip_selectors: &'a [String],
+  #[serde(skip_serializing_if = "Option::is_none")]
+  listener: Option<ListenerOptions>,
+}
+
+/// Create the initialization script.
+pub(crate) fn create(
+  options: &ScriptOptions,
+  default: &Settings,
+  windows: &[(LabelPattern, Settings)],
+) -> Script {
+  let windows = windows
+    .iter()
+    .map(|(pattern, settings)| {
+      let pattern = to_json(&regex_source(&pattern.to_regex()));
+      format!("[{pattern},{}]", table(options, settings))
+    })
+    .join(",");
+
+  let config = format!(
+    "const CONFIG={{options:{},windows:[{windows}],default:{}}};",
+    options.to_json(),
+    table(options, default)
+  );
+
+  let script = include_str!("../assets/script.js")
+    .trim()
+    .replace("/*CONFIG*/", &config);
+
+  let script = Script::from(script);
+  match &options.nonce {
+    Some(nonce) => script.with_nonce(nonce),
+    None => script,
+  }
+}
+
+/// Create a script that replaces the shortcuts and options of an already initialized webview.
+pub(crate) fn update(options: &ScriptOptions, settings: &Settings) -> String {
+  format!(
+    "window.{RUNTIME_API}?.update({},{});",
+    table(options, settings),
+    options.to_json()
+  )
+}
+
+fn table(options: &ScriptOptions, settings: &Settings) -> String {
+  let platform = options.platform;
+  // Each flag is resolved on its own so that its shortcuts can be traced back to it.
+  let flags = settings
+    .flags
+    .iter_names()
+    .flat_map(|(name, flag)| {
+      let name = options.dispatch.then_some(name);
+      flag
+        .shortcuts(platform)
+        .into_iter()
+        .map(move |it| (name, it))
+    })
+    .collect_vec();
+
+  let mut keys = Vec::new();
+  let mut pointers = Vec::new();
+  let mut sequences = Vec::new();
+
+  let custom = settings.shortcuts.iter().map(|it| (None, it));
+  for (flag, shortcut) in flags
+    .iter()
+    .map(|(name, it)| (*name, it))
+    .chain(custom)
+  {
+    match shortcut.kind() {
+      ShortcutKind::Keyboard(it) => {
+        let handler = options
+          .handlers
+          .iter()
+          .position(|handled| shortcut::is_same(handled, it));
+        let display = it.to_string();
+        let display = (options.notify || options.dispatch).then_some(display);
+        keys.extend(
+          key_entries(it, platform)
+            .into_iter()
+            .map(|entry| KeyEntry {
+              handler,
+              display: display.clone(),
+              flag,
+              ..entry
+            }),
+        );
+      }
+      ShortcutKind::Pointer(it) => {
+        pointers.push(PointerEntry {
+          event: it.event().to_string(),
+          skip_editable: it.skips_editable(),
+          skip_selectors: it.skip_selectors(),
+          listener: it.listener_options(),
+          flag,
+        });
+      }
+      ShortcutKind::Sequence(it) => {
+        sequences.push(SequenceEntry {
+          steps: it
+            .steps()
+            .iter()
+            .map(|step| {
+              key_entries(step, platform)
+                .into_iter()
+                .map(without_options)
+                .collect()
+            })
+            .collect(),
+          timeout: it.timeout().as_millis(),
+          prevent_all_steps: it.prevents_all_steps(),
+          display: it.to_string(),
+          skip_editable: it.skips_editable(),
+          skip_selectors: it.skip_selectors(),
+          listener: it.listener_options(),
+        });
+      }
+    }
+  }
+
+  let allow = match settings.mode {
+    Mode::AllowList => settings
+      .allowed
+      .iter()
+      .flat_map(|it| key_entries(it, platform))
+      .map(without_options)
+      .unique()
+      .collect(),
+    Mode::DenyList => Vec::new(),
+  };
+
+  to_json(&ShortcutTable {
+    keys: keys.into_iter().unique().collect(),
+    pointers: pointers.into_iter().unique().collect(),
+    sequences: sequences.into_iter().unique().collect(),
+    mode: settings.mode,
+    allow,
+  })
+}
+
+/// Entries for the key and the code of the shortcut, with the modifiers resolved for the platform.
+fn key_entries(shortcut: &KeyboardShortcut, platform: Platform) -> Vec<KeyEntry<'_>> {
+  let modifiers = shortcut
+    .modifiers()
+    .iter()
+    .map(|it| it.resolve(platform))
+    .unique()
+    .collect_vec();
+
+  let optional = shortcut
+    .optional_modifiers()
+    .iter()
+    .map(|it| it.resolve(platform))
+    .filter(|it| !modifiers.contains(it))
+    .unique()
+    .map(|it| it.to_string())
+    .collect_vec();
+
+  let entry = |key, code| KeyEntry {
+    key,
+    code,
+    modifiers: modifiers
+      .iter()
+      .map(ToString::to_string)
+      .collect(),
+    optional: optional.clone(),
+    skip_editable: shortcut.skips_editable(),
+    skip_selectors: shortcut.skip_selectors(),
+    listener: shortcut.listener_options(),
+    events: shortcut.events(),
+    repeat: shortcut.repeat(),
+    handler: None,
+    display: None,
+    flag: None,
+  };
+
+  let mut entries = Vec::with_capacity(2);
+  if let Some(key) = shortcut.key().map(Key::event_value) {
+    entries.push(entry(key, false));
+  }
+
+  if let Some(code) = shortcut.code() {
+    entries.push(entry(code, true));
+  }
+
+  entries
+}
+
+/// Whether both shortcuts match the same key presses on the platform, regardless of their options.
+pub(crate) fn overlaps(a: &KeyboardShortcut, b: &KeyboardShortcut, platform: Platform) -> bool {
+  let b = key_entries(b, platform)
+    .into_iter()
+    .map(without_options)
+    .collect_vec();
+
+  key_entries(a, platform)
+    .into_iter()
+    .map(without_options)
+    .any(|it| b.contains(&it))
+}
+
+/// Exemptions, listener options and events are meaningless for allowed shortcuts and for the steps
+/// of a sequence, as they are either never prevented or follow the options of the sequence.
+fn without_options(entry: KeyEntry<'_>) -> KeyEntry<'_> {
+  KeyEntry {
+    skip_editable: false,
+    skip_selectors: &[],
+    listener: None,
+    events: &[KeyboardEvent::KeyDown],
+    repeat: KeyRepeat::Any,
+    handler: None,
+    display: None,
+    flag: None,
+    ..entry
+  }
+}
+
+/// Serialize a value as JSON that can be safely embedded in the script,
+/// even if the script itself ends up inside a `<script>` tag.
+fn to_json(value: &impl Serialize) -> String {
+  serde_json::to_string(value)
+    .expect("script tables should always be serializable")
+    .replace('<', "\\u003c")
+    .replace('\u{2028}', "\\u2028")
+    .replace('\u{2029}', "\\u2029")
+}
+
+/// Source of a JavaScript regular expression literal, i.e. without the slashes around it.
+fn regex_source(literal: &str) -> String {
+  literal
+    .strip_prefix('/')
+    .and_then(|it| it.strip_suffix('/'))
+    .unwrap_or(literal)
+    .to_owned()
+}
+
+#[cfg(test)]
+mod test {
+  use crate::controller::Settings;
+  use crate::csp::{self, HashAlgorithm};
+  use crate::label::LabelPattern;
+  use crate::listener::{ListenerOptions, ListenerTarget, Propagation};
+  use crate::origin::{OriginPattern, Origins};
+  use crate::platform::Platform;
+  use crate::script::ScriptOptions;
+  use crate::shortcut::{
+    Key, KeyRepeat, KeyboardEvent, KeyboardShortcut, PointerEvent, PointerShortcut,
+    SequenceShortcut, Shortcut,
+  };
+  use crate::{Flags, Mode};
+  use std::time::Duration;
+
+  /// Settings that only prevent the given shortcuts.
+  fn settings(shortcuts: Vec<Box<dyn Shortcut>>) -> Settings {
+    Settings {
+      flags: Flags::empty(),
+      shortcuts,
+      ..Settings::default()
+    }
+  }
+
+  #[test]
+  fn update_script() {
+    let settings = settings(vec![
+      Box::new(KeyboardShortcut::with_ctrl("F")),
+      Box::new(PointerShortcut::new(PointerEvent::ContextMenu)),
+    ]);
+
+    let script = super::update(&ScriptOptions::default(), &settings);
+    assert_eq!(
+      script,
+      r#"window.__TAURI_PLUGIN_PREVENT_DEFAULT__?.update({"keys":[{"key":"f","modifiers":["ctrlKey"]}],"pointers":[{"event":"contextmenu"}]},{"origins":{"allow":[],"deny":[]},"notify":false,"skipEditable":false,"skipSelectors":[],"listener":{"target":"window","capture":false,"propagation":"continue"}});"#
+    );
+  }
+
+  #[test]
+  fn allow_list() {
+    let settings = Settings {
+      flags: Flags::empty(),
+      mode: Mode::AllowList,
+      allowed: vec![KeyboardShortcut::builder("s")
+        .cmd_or_ctrl_key()
+        .skip_editable()
+        .build()],
+      ..Settings::default()
+    };
+
+    let options = ScriptOptions {
+      platform: Platform::MacOs,
+      ..ScriptOptions::default()
+    };
+
+    let script = super::update(&options, &settings);
+    assert!(script.contains(
+      r#"{"keys":[],"pointers":[],"mode":"allowList","allow":[{"key":"s","modifiers":["metaKey"]}]}"#
+    ));
+
+    let settings = Settings { mode: Mode::DenyList, ..settings };
+    let script = super::update(&options, &settings);
+    assert!(script.contains(r#"{"keys":[],"pointers":[]}"#));
+  }
+
+  #[test]
+  fn sequences() {
+    let settings = settings(vec![Box::new(
+      SequenceShortcut::builder([KeyboardShortcut::with_cmd_or_ctrl("K")])
+        .step(
+          KeyboardShortcut::builder("S")
+            .code("KeyS")
+            .skip_editable()
+            .build(),
+        )
+        .timeout(Duration::from_millis(500))
+        .build(),
+    )]);
+
+    let options = ScriptOptions {
+      platform: Platform::Linux,
+      ..ScriptOptions::default()
+    };
+
+    let script = super::update(&options, &settings);
+    assert!(script.contains(
+      r#""sequences":[{"steps":[[{"key":"k","modifiers":["ctrlKey"]}],[{"key":"s"},{"key":"KeyS","code":true}]],"timeout":500,"display":"sequence:cmdorctrl+k s|code:keys"}]"#
+    ));
+  }
+
+  #[test]
+  fn keyboard_events() {
+    let settings = settings(vec![Box::new(
+      KeyboardShortcut::builder("Enter")
+        .events(&[KeyboardEvent::KeyUp, KeyboardEvent::BeforeInput])
+        .repeat(KeyRepeat::Never)
+        .build(),
+    )]);
+
+    let script = super::update(&ScriptOptions::default(), &settings);
+    assert!(script.contains(r#"{"key":"Enter","events":["keyup","beforeinput"],"repeat":"never"}"#));
+  }
+
+  #[test]
+  fn space_key() {
+    let settings = settings(vec![Box::new(KeyboardShortcut::with_ctrl(Key::Space))]);
+    let script = super::update(&ScriptOptions::default(), &settings);
+    assert!(script.contains(r#"{"key":" ","modifiers":["ctrlKey"]}"#));
+  }
+
+  #[test]
+  fn handlers() {
+    let editable = KeyboardShortcut::builder("P")
+      .ctrl_key()
+      .skip_editable()
+      .build();
+    let settings = settings(vec![
+      Box::new(KeyboardShortcut::with_ctrl("P")),
+      Box::new(editable.clone()),
+    ]);
+
+    let options = ScriptOptions {
+      handlers: vec![KeyboardShortcut::with_ctrl("K"), editable],
+      ..ScriptOptions::default()
+    };
+
+    // Shortcuts with the same display are told apart by their options.
+    let script = super::update(&options, &settings);
+    assert!(script.contains(r#"{"key":"p","modifiers":["ctrlKey"]}"#));
+    assert!(script.contains(r#""skipEditable":true,"handler":1}"#));
+  }
+
+  #[test]
+  fn notify_display() {
+    let settings = settings(vec![Box::new(KeyboardShortcut::with_ctrl("K"))]);
+
+    let script = super::update(&ScriptOptions::default(), &settings);
+    assert!(!script.contains(r#""display""#));
+
+    let options = ScriptOptions {
+      notify: true,
+      ..ScriptOptions::default()
+    };
+
+    let script = super::update(&options, &settings);
+    assert!(script.contains(r#"{"key":"k","modifiers":["ctrlKey"],"display":"keyboard:ctrl+k"}"#));
+  }
+
+  #[test]
+  fn dispatch_blocked() {
+    let settings = Settings {
+      flags: Flags::CONTEXT_MENU,
+      shortcuts: vec![Box::new(KeyboardShortcut::with_ctrl("K"))],
+      ..Settings::default()
+    };
+
+    let script = super::update(&ScriptOptions::default(), &settings);
+    assert!(!script.contains(r#""flag""#));
+
+    let options = ScriptOptions {
+      dispatch: true,
+      ..ScriptOptions::default()
+    };
+    let script = super::update(&options, &settings);
+    assert!(script.contains(r#""pointers":[{"event":"contextmenu","flag":"CONTEXT_MENU"}]"#));
+    assert!(script.contains(r#"{"key":"k","modifiers":["ctrlKey"],"display":"keyboard:ctrl+k"}"#));
+    assert!(script.contains(r#""notify":false,"dispatch":true"#));
+  }
+
+  #[test]
+  fn skip_editable() {
+    let settings = settings(vec![Box::new(
+      KeyboardShortcut::builder("Backspace")
+        .skip_editable()
+        .build(),
+    )]);
+
+    let script = super::update(&ScriptOptions::default(), &settings);
+    assert!(script.contains(r#"{"key":"Backspace","skipEditable":true}"#));
+  }
+
+  #[test]
+  fn platform_tables() {
+    let settings = Settings {
+      flags: Flags::FIND | Flags::NAVIGATION,
+      shortcuts: vec![Box::new(KeyboardShortcut::with_cmd_or_ctrl("K"))],
+      ..Settings::default()
+    };
+
+    let script = |platform| {
+      let options = ScriptOptions { platform, ..ScriptOptions::default() };
+      super::update(&options, &settings)
+    };
+
+    let macos = script(Platform::MacOs);
+    assert!(macos.contains(r#"{"key":"f","modifiers":["metaKey"]}"#));
+    assert!(macos.contains(r#"{"key":"k","modifiers":["metaKey"]}"#));
+    assert!(macos.contains(r#"{"key":"[","modifiers":["metaKey"]}"#));
+    assert!(!macos.contains("ArrowLeft"));
+
+    let linux = script(Platform::Linux);
+    assert!(linux.contains(r#"{"key":"f","modifiers":["ctrlKey"]}"#));
+    assert!(linux.contains(r#"{"key":"k","modifiers":["ctrlKey"]}"#));
+    assert!(linux.contains(r#"{"key":"ArrowLeft","modifiers":["altKey"]}"#));
+    assert!(!linux.contains(r#""modifiers":["metaKey"]"#));
+  }
+
+  #[test]
+  fn keyboard_code() {
+    let settings = settings(vec![
+      Box::new(KeyboardShortcut::with_code("Digit1")),
+      Box::new(
+        KeyboardShortcut::builder("f")
+          .code("KeyF")
+          .ctrl_key()
+          .build(),
+      ),
+    ]);
+
+    let script = super::update(&ScriptOptions::default(), &settings);
+    assert!(script.contains(
+      r#"[{"key":"Digit1","code":true},{"key":"f","modifiers":["ctrlKey"]},{"key":"KeyF","code":true,"modifiers":["ctrlKey"]}]"#
+    ));
+  }
+
+  #[test]
+  fn optional_modifiers() {
+    let settings = settings(vec![
+      Box::new(KeyboardShortcut::any_modifiers("F5")),
+      Box::new(
+        KeyboardShortcut::builder("r")
+          .ctrl_key()
+          .any_modifiers()
+          .build(),
+      ),
+    ]);
+
+    let script = super::update(&ScriptOptions::default(), &settings);
+    assert!(script.contains(
+      r#"[{"key":"F5","optional":["ctrlKey","shiftKey","altKey","metaKey"]},{"key":"r","modifiers":["ctrlKey"],"optional":["shiftKey","altKey","metaKey"]}]"#
+    ));
+  }
+
+  #[test]
+  fn exemptions() {
+    let settings = settings(vec![
+      Box::new(
+        KeyboardShortcut::builder("a")
+          .ctrl_key()
+          .skip_editable()
+          .build(),
+      ),
+      Box::new(
+        PointerShortcut::builder(PointerEvent::ContextMenu)
+          .skip_selector(".allow-native-menu")
+          .skip_selector(r#"[title="it's"]"#)
+          .build(),
+      ),
+    ]);
+
+    let options = ScriptOptions {
+      skip_selectors: vec![String::from("[data-editor]")],
+      ..ScriptOptions::default()
+    };
+
+    let script = super::update(&options, &settings);
+    assert!(script.contains(r#"{"key":"a","modifiers":["ctrlKey"],"skipEditable":true}"#));
+    assert!(script.contains(
+      r#"{"event":"contextmenu","skipSelectors":[".allow-native-menu","[title=\"it's\"]"]}"#
+    ));
+    assert!(script.contains(r#""skipEditable":false,"skipSelectors":["[data-editor]"],"#));
+  }
+
+  #[test]
+  fn listener_options() {
+    let document = ListenerOptions::new()
+      .target(ListenerTarget::Document)
+      .capture(true)
+      .propagation(Propagation::StopImmediate);
+
+    let settings = settings(vec![
+      Box::new(KeyboardShortcut::with_ctrl("a")),
+      Box::new(
+        KeyboardShortcut::builder("b")
+          .ctrl_key()
+          .listener_options(document)
+          .build(),
+      ),
+      Box::new(
+        PointerShortcut::builder(PointerEvent::ContextMenu)
+          .listener_options(document)
+          .build(),
+      ),
+    ]);
+
+    let options = ScriptOptions {
+      listener: ListenerOptions::new().propagation(Propagation::Stop),
+      ..ScriptOptions::default()
+    };
+
+    let script = super::update(&options, &settings);
+    assert!(script.contains(r#"{"key":"a","modifiers":["ctrlKey"]}"#));
+    assert!(script.contains(
+      r#"{"key":"b","modifiers":["ctrlKey"],"listener":{"target":"document","capture":true,"propagation":"stopImmediate"}}"#
+    ));
+    assert!(script.contains(
+      r#"{"event":"contextmenu","listener":{"target":"document","capture":true,"propagation":"stopImmediate"}}"#
+    ));
+    assert!(
+      script.contains(r#""listener":{"target":"window","capture":false,"propagation":"stop"}}"#)
+    );
+  }
+
+  #[test]
+  fn window_settings() {
+    let default = settings(vec![Box::new(KeyboardShortcut::with_ctrl("F"))]);
+
+    let docs = settings(Vec::new());
+
+    let script = super::create(
+      &ScriptOptions::default(),
+      &default,
+      &[(LabelPattern::new("docs-*"), docs)],
+    );
+    assert!(script.contains(
+      r#"windows:[["^docs-.*$",{"keys":[],"pointers":[]}]],default:{"keys":[{"key":"f","modifiers":["ctrlKey"]}],"pointers":[]}};"#
+    ));
+  }
+
+  #[test]
+  fn origins() {
+    let settings = settings(Vec::new());
+
+    let origins = Origins {
+      allow: vec![
+        OriginPattern::parse("tauri://localhost").unwrap(),
+        OriginPattern::parse("http://localhost:*").unwrap(),
+      ],
+      deny: vec![OriginPattern::parse("http://localhost:3000").unwrap()],
+    };
+
+    let options = ScriptOptions { origins, ..ScriptOptions::default() };
+    let script = super::create(&options, &settings, &[]);
+    assert!(script.contains(
+      r#"const CONFIG={options:{"origins":{"allow":["^tauri:\\/\\/localhost$","^http:\\/\\/localhost(:\\d+)?$"],"deny":["^http:\\/\\/localhost:3000$"]},"#
+    ));
+  }
+
+  #[test]
+  fn duplicates() {
+    let settings = Settings {
+      flags: Flags::CONTEXT_MENU,
+      shortcuts: vec![
+        Box::new(KeyboardShortcut::with_ctrl("F")),
+        Box::new(KeyboardShortcut::with_ctrl("F")),
+        Box::new(PointerShortcut::new(PointerEvent::ContextMenu)),
+      ],
+      ..Settings::default()
+    };
+
+    let script = super::update(&ScriptOptions::default(), &settings);
+    assert_eq!(script.matches(r#"{"key":"f""#).count(), 1);
+    assert_eq!(
+      script
+        .matches(r#"{"event":"contextmenu"}"#)
+        .count(),
+      1
+    );
+  }
+
+  #[test]
+  fn hostile_keys() {
+    let windows = [(LabelPattern::new("</script>"), settings(Vec::new()))];
+    let settings = settings(vec![
+      Box::new(KeyboardShortcut::new(r"a');alert(1);//")),
+      Box::new(KeyboardShortcut::new(r#"\"});alert(2);//"#)),
+      Box::new(KeyboardShortcut::new("</script><script>alert(3)</script>")),
+      Box::new(
+        PointerShortcut::builder(PointerEvent::ContextMenu)
+          .skip_selector("\u{2028}alert(4)")
+          .build(),
+      ),
+    ]);
+
+    let script = super::update(&ScriptOptions::default(), &settings);
+    assert_eq!(
+      script,
+      r#"window.__TAURI_PLUGIN_PREVENT_DEFAULT__?.update({"keys":[{"key":"a');alert(1);//"},{"key":"\\\"});alert(2);//"},{"key":"\u003c/script>\u003cscript>alert(3)\u003c/script>"}],"pointers":[{"event":"contextmenu","skipSelectors":["\u2028alert(4)"]}]},{"origins":{"allow":[],"deny":[]},"notify":false,"skipEditable":false,"skipSelectors":[],"listener":{"target":"window","capture":false,"propagation":"continue"}});"#
+    );
+
+    let script = super::create(&ScriptOptions::default(), &settings, &windows);
+    assert!(script.contains(
+      r#"windows:[["^\u003c\\/script>$",{"keys":[],"pointers":[]}]],default:{"keys":[{"key":"a');alert(1);//"},{"key":"\\\"});alert(2);//"},{"key":"\u003c/script>\u003cscript>alert(3)\u003c/script>"}],"pointers":[{"event":"contextmenu","skipSelectors":["\u2028alert(4)"]}]}};"#
+    ));
+    assert!(!script.contains("</script>"));
+    assert!(!script.contains('\u{2028}'));
+  }
+
+  #[test]
+  fn hash_of_html_tag() {
+    let settings = settings(vec![Box::new(KeyboardShortcut::new("</script>"))]);
+    let script = super::create(&ScriptOptions::default(), &settings, &[]);
+    let html = script.to_html();
+    let content = html
+      .strip_prefix("<script>")
+      .and_then(|it| it.strip_suffix("</script>"))
+      .unwrap();
+
+    assert_eq!(
+      csp::hash(content, HashAlgorithm::Sha256),
+      script.hash(HashAlgorithm::Sha256)
+    );
+  }
+
+  #[test]
+  fn html_tag() {
+    let script = super::Script::new("update();");
+    assert_eq!(script.to_html(), "<script>update();</script>");
+
+    let script = script.with_nonce(r#"a"b"#);
+    assert_eq!(
+      script.to_html(),
+      r#"<script nonce="a&quot;b">update();</script>"#
+    );
   }
 }
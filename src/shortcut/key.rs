@@ -0,0 +1,271 @@
+use serde::de::Deserializer;
+use serde::{Deserialize, Serialize, Serializer};
+use strum::{AsRefStr, Display, EnumString};
+
+/// Value of `KeyboardEvent.key`.
+///
+/// Parsing is case-insensitive and accepts a few common aliases, such as `Esc` or `Del`.
+/// Anything else becomes [`Key::Other`], so any key can still be used.
+///
+/// # Examples
+/// ```
+/// use tauri_plugin_prevent_default::Key;
+///
+/// assert_eq!(Key::from("esc"), Key::Escape);
+/// assert_eq!(Key::from("F"), Key::F);
+/// assert_eq!(Key::from("ç"), Key::Other(String::from("ç")));
+/// assert_eq!(Key::ArrowLeft.as_str(), "ArrowLeft");
+/// ```
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, AsRefStr, Display, EnumString)]
+#[strum(ascii_case_insensitive)]
+pub enum Key {
+  // Letters
+  #[strum(to_string = "a")]
+  A,
+  #[strum(to_string = "b")]
+  B,
+  #[strum(to_string = "c")]
+  C,
+  #[strum(to_string = "d")]
+  D,
+  #[strum(to_string = "e")]
+  E,
+  #[strum(to_string = "f")]
+  F,
+  #[strum(to_string = "g")]
+  G,
+  #[strum(to_string = "h")]
+  H,
+  #[strum(to_string = "i")]
+  I,
+  #[strum(to_string = "j")]
+  J,
+  #[strum(to_string = "k")]
+  K,
+  #[strum(to_string = "l")]
+  L,
+  #[strum(to_string = "m")]
+  M,
+  #[strum(to_string = "n")]
+  N,
+  #[strum(to_string = "o")]
+  O,
+  #[strum(to_string = "p")]
+  P,
+  #[strum(to_string = "q")]
+  Q,
+  #[strum(to_string = "r")]
+  R,
+  #[strum(to_string = "s")]
+  S,
+  #[strum(to_string = "t")]
+  T,
+  #[strum(to_string = "u")]
+  U,
+  #[strum(to_string = "v")]
+  V,
+  #[strum(to_string = "w")]
+  W,
+  #[strum(to_string = "x")]
+  X,
+  #[strum(to_string = "y")]
+  Y,
+  #[strum(to_string = "z")]
+  Z,
+
+  // Digits
+  #[strum(to_string = "0")]
+  Digit0,
+  #[strum(to_string = "1")]
+  Digit1,
+  #[strum(to_string = "2")]
+  Digit2,
+  #[strum(to_string = "3")]
+  Digit3,
+  #[strum(to_string = "4")]
+  Digit4,
+  #[strum(to_string = "5")]
+  Digit5,
+  #[strum(to_string = "6")]
+  Digit6,
+  #[strum(to_string = "7")]
+  Digit7,
+  #[strum(to_string = "8")]
+  Digit8,
+  #[strum(to_string = "9")]
+  Digit9,
+
+  // Function keys
+  F1,
+  F2,
+  F3,
+  F4,
+  F5,
+  F6,
+  F7,
+  F8,
+  F9,
+  F10,
+  F11,
+  F12,
+  F13,
+  F14,
+  F15,
+  F16,
+  F17,
+  F18,
+  F19,
+  F20,
+  F21,
+  F22,
+  F23,
+  F24,
+
+  // Navigation
+  #[strum(to_string = "ArrowDown", serialize = "Down")]
+  ArrowDown,
+  #[strum(to_string = "ArrowLeft", serialize = "Left")]
+  ArrowLeft,
+  #[strum(to_string = "ArrowRight", serialize = "Right")]
+  ArrowRight,
+  #[strum(to_string = "ArrowUp", serialize = "Up")]
+  ArrowUp,
+  End,
+  Home,
+  PageDown,
+  PageUp,
+  Tab,
+
+  // Editing
+  Backspace,
+  #[strum(to_string = "Delete", serialize = "Del")]
+  Delete,
+  #[strum(to_string = "Enter", serialize = "Return")]
+  Enter,
+  #[strum(to_string = "Escape", serialize = "Esc")]
+  Escape,
+  Insert,
+  #[strum(serialize = " ", serialize = "Spacebar", to_string = "Space")]
+  Space,
+
+  // Symbols
+  #[strum(to_string = "=", serialize = "Equal")]
+  Equal,
+  #[strum(to_string = "+", serialize = "Plus")]
+  Plus,
+  #[strum(to_string = "-", serialize = "Minus")]
+  Minus,
+  #[strum(to_string = "_", serialize = "Underscore")]
+  Underscore,
+  #[strum(to_string = "[", serialize = "BracketLeft")]
+  BracketLeft,
+  #[strum(to_string = "]", serialize = "BracketRight")]
+  BracketRight,
+
+  // UI and device
+  CapsLock,
+  ContextMenu,
+  NumLock,
+  Pause,
+  PrintScreen,
+  ScrollLock,
+
+  // Browser
+  BrowserBack,
+  BrowserFavorites,
+  BrowserForward,
+  BrowserHome,
+  BrowserRefresh,
+  BrowserSearch,
+  BrowserStop,
+
+  // Media
+  AudioVolumeDown,
+  AudioVolumeMute,
+  AudioVolumeUp,
+  MediaPlayPause,
+  MediaStop,
+  MediaTrackNext,
+  MediaTrackPrevious,
+
+  /// Any other value of `KeyboardEvent.key`.
+  #[strum(default, transparent)]
+  Other(String),
+}
+
+impl Key {
+  /// Canonical name of the key, as found in `KeyboardEvent.key`,
+  /// except for [`Key::Space`] which is named `Space` so that it can be displayed.
+  pub fn as_str(&self) -> &str {
+    self.as_ref()
+  }
+
+  /// Value of `KeyboardEvent.key` for this key.
+  pub(crate) fn event_value(&self) -> &str {
+    match self {
+      Self::Space => " ",
+      _ => self.as_str(),
+    }
+  }
+}
+
+impl From<&String> for Key {
+  fn from(value: &String) -> Self {
+    Key::from(value.as_str())
+  }
+}
+
+impl From<String> for Key {
+  fn from(value: String) -> Self {
+    Key::from(value.as_str())
+  }
+}
+
+impl Serialize for Key {
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    serializer.serialize_str(self.as_str())
+  }
+}
+
+impl<'de> Deserialize<'de> for Key {
+  fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+  where
+    D: Deserializer<'de>,
+  {
+    String::deserialize(deserializer).map(Key::from)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::Key;
+
+  #[test]
+  fn from_str() {
+    assert_eq!(Key::from("a"), Key::A);
+    assert_eq!(Key::from("A"), Key::A);
+    assert_eq!(Key::from("0"), Key::Digit0);
+    assert_eq!(Key::from("f12"), Key::F12);
+    assert_eq!(Key::from("Esc"), Key::Escape);
+    assert_eq!(Key::from("left"), Key::ArrowLeft);
+    assert_eq!(Key::from(" "), Key::Space);
+    assert_eq!(Key::from("["), Key::BracketLeft);
+    assert_eq!(Key::from("Dead"), Key::Other(String::from("Dead")));
+  }
+
+  #[test]
+  fn canonical_name() {
+    assert_eq!(Key::A.as_str(), "a");
+    assert_eq!(Key::Digit9.as_str(), "9");
+    assert_eq!(Key::from("esc").as_str(), "Escape");
+    assert_eq!(Key::from("return").to_string(), "Enter");
+    assert_eq!(Key::Other(String::from("Dead")).as_str(), "Dead");
+    assert_eq!(Key::Other(String::from("Dead")).to_string(), "Dead");
+    assert_eq!(Key::Space.as_str(), "Space");
+    assert_eq!(Key::Space.event_value(), " ");
+  }
+}
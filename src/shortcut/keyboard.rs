@@ -1,28 +1,115 @@
-use super::ModifierKey;
+use super::{values, Key, ModifierKey};
 use crate::display;
+use crate::error::Error;
+use crate::listener::ListenerOptions;
+use serde::{Deserialize, Serialize};
 use std::fmt;
+use std::str::FromStr;
+use strum::{Display as EnumDisplay, EnumIs, EnumString};
 
-#[derive(Debug)]
+/// Keyboard event whose default action is prevented.
+#[non_exhaustive]
+#[derive(
+  Clone, Copy, Debug, PartialEq, Eq, Hash, EnumDisplay, EnumIs, EnumString, Deserialize, Serialize,
+)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum KeyboardEvent {
+  /// The key was pressed, or is being held down.
+  KeyDown,
+  /// The key was released.
+  KeyUp,
+  /// A key that produces a character was pressed. Deprecated, but still fired by most engines.
+  KeyPress,
+  /// The pressed key is about to modify an editable element.
+  ///
+  /// As this is not a keyboard event, the key and the modifiers are those of the key being held down.
+  BeforeInput,
+}
+
+/// Which presses of a key are prevented when it is held down.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, EnumIs, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum KeyRepeat {
+  /// Both the first press and the automatic repetitions.
+  #[default]
+  Any,
+  /// Only the automatic repetitions, letting the first press through.
+  Only,
+  /// Only the first press, letting the automatic repetitions through.
+  Never,
+}
+
+/// Keyboard shortcut, matched against the layout key (`KeyboardEvent.key`),
+/// the physical key (`KeyboardEvent.code`), or both.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(try_from = "RawKeyboardShortcut")]
 pub struct KeyboardShortcut {
-  key: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  key: Option<Key>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  code: Option<String>,
   modifiers: Vec<ModifierKey>,
+  #[serde(rename = "optionalModifiers", skip_serializing_if = "Vec::is_empty")]
+  optional_modifiers: Vec<ModifierKey>,
+  #[serde(rename = "skipEditable", skip_serializing_if = "crate::util::is_false")]
+  skip_editable: bool,
+  #[serde(rename = "skipSelectors", skip_serializing_if = "Vec::is_empty")]
+  skip_selectors: Vec<String>,
+  #[serde(rename = "listenerOptions", skip_serializing_if = "Option::is_none")]
+  listener_options: Option<ListenerOptions>,
+  #[serde(skip_serializing_if = "is_keydown_only")]
+  events: Vec<KeyboardEvent>,
+  #[serde(skip_serializing_if = "KeyRepeat::is_any")]
+  repeat: KeyRepeat,
 }
 
 impl KeyboardShortcut {
-  pub fn new(key: impl AsRef<str>) -> Self {
-    Self {
-      key: key.as_ref().to_owned(),
-      modifiers: Vec::new(),
-    }
+  pub fn new(key: impl Into<Key>) -> Self {
+    Self::builder(key).build()
+  }
+
+  /// Create a new keyboard shortcut matched against the physical key, such as `KeyF` or `Digit1`,
+  /// regardless of the keyboard layout.
+  ///
+  /// <https://developer.mozilla.org/en-US/docs/Web/API/UI_Events/Keyboard_event_code_values>
+  ///
+  /// # Examples
+  /// ```
+  /// use tauri_plugin_prevent_default::KeyboardShortcut;
+  ///
+  /// tauri_plugin_prevent_default::Builder::new()
+  ///   .shortcut(KeyboardShortcut::with_code("KeyF"))
+  ///   .shortcut(KeyboardShortcut::builder("1").code("Digit1").shift_key().build())
+  ///   .build();
+  /// ```
+  pub fn with_code(code: impl AsRef<str>) -> Self {
+    KeyboardShortcutBuilder::from_code(code).build()
   }
 
   /// Initialize a new keyboard shortcut builder with the specified key.
-  pub fn builder(key: impl AsRef<str>) -> KeyboardShortcutBuilder {
+  pub fn builder(key: impl Into<Key>) -> KeyboardShortcutBuilder {
     KeyboardShortcutBuilder::new(key)
   }
 
+  /// Create a new keyboard shortcut that matches the key regardless of the modifiers being pressed.
+  ///
+  /// # Examples
+  /// ```
+  /// use tauri_plugin_prevent_default::KeyboardShortcut;
+  ///
+  /// // Prevents `F5`, `Ctrl+F5`, `Shift+F5`, `Ctrl+Shift+F5` and so on.
+  /// tauri_plugin_prevent_default::Builder::new()
+  ///   .shortcut(KeyboardShortcut::any_modifiers("F5"))
+  ///   .build();
+  /// ```
+  pub fn any_modifiers(key: impl Into<Key>) -> Self {
+    Self::builder(key).any_modifiers().build()
+  }
+
   /// Create a new keyboard shortcut with the specified key and modifiers.
-  pub fn with_modifiers(key: impl AsRef<str>, modifiers: &[ModifierKey]) -> Self {
+  pub fn with_modifiers(key: impl Into<Key>, modifiers: &[ModifierKey]) -> Self {
     Self::builder(key).modifiers(modifiers).build()
   }
 
@@ -39,85 +126,365 @@ impl KeyboardShortcut {
   ///   .shortcut(KeyboardShortcut::with_modifiers("A", &[AltKey]))
   ///   .build();
   /// ```
-  pub fn with_alt(key: impl AsRef<str>) -> Self {
+  pub fn with_alt(key: impl Into<Key>) -> Self {
     Self::builder(key).alt_key().build()
   }
 
+  /// Create a new keyboard shortcut with the specified key and the `CmdOrCtrl` modifier.
+  pub fn with_cmd_or_ctrl(key: impl Into<Key>) -> Self {
+    Self::builder(key).cmd_or_ctrl_key().build()
+  }
+
   /// Create a new keyboard shortcut with the specified key and the `CtrlKey` modifier.
-  pub fn with_ctrl(key: impl AsRef<str>) -> Self {
+  pub fn with_ctrl(key: impl Into<Key>) -> Self {
     Self::builder(key).ctrl_key().build()
   }
 
   /// Create a new keyboard shortcut with the specified key and the `CtrlKey` and `MetaKey` modifiers.
-  pub fn with_ctrl_meta(key: impl AsRef<str>) -> Self {
+  pub fn with_ctrl_meta(key: impl Into<Key>) -> Self {
     Self::builder(key)
       .modifiers(&[ModifierKey::CtrlKey, ModifierKey::MetaKey])
       .build()
   }
 
   /// Create a new keyboard shortcut with the specified key and the `CtrlKey` and `ShiftKey` modifiers.
-  pub fn with_ctrl_shift(key: impl AsRef<str>) -> Self {
+  pub fn with_ctrl_shift(key: impl Into<Key>) -> Self {
     Self::builder(key)
       .modifiers(&[ModifierKey::CtrlKey, ModifierKey::ShiftKey])
       .build()
   }
 
   /// Create a new keyboard shortcut with the specified key and the `MetaKey` modifier.
-  pub fn with_meta(key: impl AsRef<str>) -> Self {
+  pub fn with_meta(key: impl Into<Key>) -> Self {
     Self::builder(key).meta_key().build()
   }
 
   /// Create a new keyboard shortcut with the specified key and the `ShiftKey` modifier.
-  pub fn with_shift(key: impl AsRef<str>) -> Self {
+  pub fn with_shift(key: impl Into<Key>) -> Self {
     Self::builder(key).shift_key().build()
   }
 
   /// Create a new keyboard shortcut with the specified key and the `ShiftKey` and `AltKey` modifiers.
-  pub fn with_shift_alt(key: impl AsRef<str>) -> Self {
+  pub fn with_shift_alt(key: impl Into<Key>) -> Self {
     Self::builder(key)
       .modifiers(&[ModifierKey::ShiftKey, ModifierKey::AltKey])
       .build()
   }
 
   /// Create a new keyboard shortcut with the specified key and the `ShiftKey` and `MetaKey` modifiers.
-  pub fn with_shift_meta(key: impl AsRef<str>) -> Self {
+  pub fn with_shift_meta(key: impl Into<Key>) -> Self {
     Self::builder(key)
       .modifiers(&[ModifierKey::ShiftKey, ModifierKey::MetaKey])
       .build()
   }
 
-  /// The key of the shortcut.
-  pub fn key(&self) -> &str {
-    &self.key
+  /// The layout key of the shortcut, if any.
+  pub fn key(&self) -> Option<&Key> {
+    self.key.as_ref()
+  }
+
+  /// The physical key of the shortcut, if any.
+  pub fn code(&self) -> Option<&str> {
+    self.code.as_deref()
   }
 
   /// The modifiers of the shortcut.
+  ///
+  /// Modifiers that are neither [required](Self::modifiers) nor
+  /// [optional](Self::optional_modifiers) must not be pressed.
   pub fn modifiers(&self) -> &[ModifierKey] {
     self.modifiers.as_slice()
   }
+
+  /// The modifiers that may or may not be pressed.
+  pub fn optional_modifiers(&self) -> &[ModifierKey] {
+    self.optional_modifiers.as_slice()
+  }
+
+  /// Whether the shortcut is allowed while the focus is in an editable element.
+  pub fn skips_editable(&self) -> bool {
+    self.skip_editable
+  }
+
+  /// CSS selectors of the elements in which the shortcut is allowed.
+  pub fn skip_selectors(&self) -> &[String] {
+    &self.skip_selectors
+  }
+
+  /// Listener options that override those of the [`Builder`](crate::Builder) for this shortcut.
+  pub fn listener_options(&self) -> Option<ListenerOptions> {
+    self.listener_options
+  }
+
+  /// The events whose default action is prevented. Only `keydown` by default.
+  pub fn events(&self) -> &[KeyboardEvent] {
+    &self.events
+  }
+
+  /// Which presses of the key are prevented when it is held down.
+  pub fn repeat(&self) -> KeyRepeat {
+    self.repeat
+  }
+
+  /// Check that the key and the code are values that `KeyboardEvent` can actually have.
+  pub(crate) fn validate(&self) -> Result<(), Error> {
+    let shortcut = || self.to_string();
+    let other = match &self.key {
+      Some(Key::Other(key)) => Some(key),
+      _ => None,
+    };
+
+    for value in [other, self.code.as_ref()].into_iter().flatten() {
+      if value.trim().is_empty() {
+        return Err(Error::EmptyKey { shortcut: shortcut() });
+      }
+    }
+
+    if let Some(key) = other {
+      if !values::is_key_value(key) {
+        let key = key.clone();
+        return Err(Error::UnknownKey { shortcut: shortcut(), key });
+      }
+    }
+
+    if let Some(code) = self.code.as_deref() {
+      if !values::is_code_value(code) {
+        let code = code.to_owned();
+        return Err(Error::UnknownCode { shortcut: shortcut(), code });
+      }
+    }
+
+    let contradictory = |reason: &str| Error::ContradictoryShortcut {
+      shortcut: shortcut(),
+      reason: reason.to_owned(),
+    };
+
+    if self.events.is_empty() {
+      return Err(contradictory("no event to prevent"));
+    }
+
+    if self.repeat.is_only() && self.events.iter().all(KeyboardEvent::is_key_up) {
+      return Err(contradictory(
+        "only repeated presses are prevented, but keyup is never repeated",
+      ));
+    }
+
+    Ok(())
+  }
+}
+
+/// Whether the events are the default ones, so that they can be left out when serializing.
+pub(crate) fn is_keydown_only(events: &[KeyboardEvent]) -> bool {
+  events == [KeyboardEvent::KeyDown]
+}
+
+fn default_events() -> Vec<KeyboardEvent> {
+  vec![KeyboardEvent::KeyDown]
 }
 
 impl fmt::Display for KeyboardShortcut {
   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-    write!(f, "{}", display::keyboard(&self.key, &self.modifiers))
+    let (modifiers, optional) = (&self.modifiers, &self.optional_modifiers);
+    let key = self.key.as_ref().map(Key::as_str);
+    let display = match &self.code {
+      Some(code) => display::keyboard_code(key, code, modifiers, optional),
+      None => display::keyboard_optional(key.unwrap_or_default(), modifiers, optional),
+    };
+
+    write!(f, "{display}")
+  }
+}
+
+#[derive(Deserialize)]
+struct RawKeyboardShortcut {
+  #[serde(default)]
+  key: Option<Key>,
+  #[serde(default)]
+  code: Option<String>,
+  #[serde(default)]
+  modifiers: Vec<ModifierKey>,
+  #[serde(default, rename = "optionalModifiers")]
+  optional_modifiers: Vec<ModifierKey>,
+  #[serde(default, rename = "skipEditable")]
+  skip_editable: bool,
+  #[serde(default, rename = "skipSelectors")]
+  skip_selectors: Vec<String>,
+  #[serde(default, rename = "listenerOptions")]
+  listener_options: Option<ListenerOptions>,
+  #[serde(default = "default_events")]
+  events: Vec<KeyboardEvent>,
+  #[serde(default)]
+  repeat: KeyRepeat,
+}
+
+impl TryFrom<RawKeyboardShortcut> for KeyboardShortcut {
+  type Error = Error;
+
+  fn try_from(raw: RawKeyboardShortcut) -> Result<Self, Self::Error> {
+    let key = raw.key.as_ref().map(Key::as_str);
+    let is_empty = |it: Option<&str>| it.map_or(true, str::is_empty);
+    if is_empty(key) && is_empty(raw.code.as_deref()) {
+      return Err(Error::InvalidKeyboardShortcut {
+        shortcut: display::keyboard("", &raw.modifiers),
+        reason: String::from("missing key"),
+      });
+    }
+
+    Ok(Self {
+      key: raw.key,
+      code: raw.code,
+      modifiers: raw.modifiers,
+      optional_modifiers: raw.optional_modifiers,
+      skip_editable: raw.skip_editable,
+      skip_selectors: raw.skip_selectors,
+      listener_options: raw.listener_options,
+      events: raw.events,
+      repeat: raw.repeat,
+    })
+  }
+}
+
+impl FromStr for KeyboardShortcut {
+  type Err = Error;
+
+  /// Parse a shortcut such as `Ctrl+Shift+I`, `shift+f5` or `keyboard:ctrl+shift+i`.
+  ///
+  /// Modifiers are case-insensitive and may also be written as `Control`, `Option`, `Cmd` or `Super`.
+  /// The physical key may be given with the `code:` prefix, as in `Ctrl+code:KeyF` or `Ctrl+F|code:KeyF`.
+  ///
+  /// Optional modifiers are followed by `?`, as in `Ctrl+Shift?+F5`,
+  /// and `*` makes all of them optional, as in `*+F5`.
+  fn from_str(value: &str) -> Result<Self, Self::Err> {
+    let invalid = |reason: &str| Error::InvalidKeyboardShortcut {
+      shortcut: value.to_owned(),
+      reason: reason.to_owned(),
+    };
+
+    let shortcut = value.trim();
+    let shortcut = shortcut
+      .get(..9)
+      .filter(|prefix| prefix.eq_ignore_ascii_case("keyboard:"))
+      .map_or(shortcut, |_| &shortcut[9..]);
+
+    // The `+` key itself, as in `Ctrl++`.
+    let (modifiers, key) = if shortcut == "+" {
+      ("", "+")
+    } else if let Some(modifiers) = shortcut.strip_suffix("++") {
+      (modifiers, "+")
+    } else {
+      shortcut
+        .rsplit_once('+')
+        .unwrap_or(("", shortcut))
+    };
+
+    let key = key.trim();
+    let (key, code) = if let Some((key, code)) = key.split_once("|code:") {
+      (Some(key), Some(code))
+    } else if let Some(code) = key
+      .get(..5)
+      .filter(|prefix| prefix.eq_ignore_ascii_case("code:"))
+      .map(|_| &key[5..])
+    {
+      (None, Some(code))
+    } else {
+      (Some(key), None)
+    };
+
+    if key.is_some_and(str::is_empty) || code.is_some_and(str::is_empty) {
+      return Err(invalid("missing key"));
+    }
+
+    let mut builder = KeyboardShortcutBuilder {
+      key: key.map(Key::from),
+      code: code.map(ToOwned::to_owned),
+      modifiers: Vec::new(),
+      optional_modifiers: Vec::new(),
+      skip_editable: false,
+      skip_selectors: Vec::new(),
+      listener_options: None,
+      events: default_events(),
+      repeat: KeyRepeat::default(),
+    };
+
+    if !modifiers.is_empty() {
+      for modifier in modifiers.split('+').map(str::trim) {
+        if modifier == "*" {
+          builder = builder.any_modifiers();
+          continue;
+        }
+
+        let (name, is_optional) = match modifier.strip_suffix('?') {
+          Some(name) => (name.trim_end(), true),
+          None => (modifier, false),
+        };
+
+        let modifier = ModifierKey::from_str(name)
+          .map_err(|_| invalid(&format!("unknown modifier \"{modifier}\"")))?;
+
+        builder = if is_optional {
+          builder.optional_modifier(modifier)
+        } else {
+          builder.modifier(modifier)
+        };
+      }
+    }
+
+    Ok(builder.build())
   }
 }
 
 #[derive(Debug)]
 pub struct KeyboardShortcutBuilder {
-  key: String,
+  key: Option<Key>,
+  code: Option<String>,
   modifiers: Vec<ModifierKey>,
+  optional_modifiers: Vec<ModifierKey>,
+  skip_editable: bool,
+  skip_selectors: Vec<String>,
+  listener_options: Option<ListenerOptions>,
+  events: Vec<KeyboardEvent>,
+  repeat: KeyRepeat,
 }
 
 impl KeyboardShortcutBuilder {
   /// Create a new keyboard shortcut builder with the specified key.
-  pub fn new(key: impl AsRef<str>) -> Self {
+  pub fn new(key: impl Into<Key>) -> Self {
+    Self {
+      key: Some(key.into()),
+      code: None,
+      modifiers: Vec::new(),
+      optional_modifiers: Vec::new(),
+      skip_editable: false,
+      skip_selectors: Vec::new(),
+      listener_options: None,
+      events: default_events(),
+      repeat: KeyRepeat::default(),
+    }
+  }
+
+  /// Create a new keyboard shortcut builder with the specified physical key.
+  pub fn from_code(code: impl AsRef<str>) -> Self {
     Self {
-      key: key.as_ref().to_owned(),
+      key: None,
+      code: Some(code.as_ref().to_owned()),
       modifiers: Vec::new(),
+      optional_modifiers: Vec::new(),
+      skip_editable: false,
+      skip_selectors: Vec::new(),
+      listener_options: None,
+      events: default_events(),
+      repeat: KeyRepeat::default(),
     }
   }
 
+  /// Also match the physical key, such as `KeyF`, so that the shortcut
+  /// is recognized even if the layout produces a different key.
+  #[must_use]
+  pub fn code(mut self, code: impl AsRef<str>) -> Self {
+    self.code = Some(code.as_ref().to_owned());
+    self
+  }
+
   /// Add a modifier to the shortcut.
   #[must_use]
   pub fn modifier(mut self, modifier: ModifierKey) -> Self {
@@ -125,6 +492,30 @@ impl KeyboardShortcutBuilder {
     self
   }
 
+  /// Add a modifier that may or may not be pressed.
+  #[must_use]
+  pub fn optional_modifier(mut self, modifier: ModifierKey) -> Self {
+    self.optional_modifiers.push(modifier);
+    self
+  }
+
+  /// Add multiple modifiers that may or may not be pressed.
+  #[must_use]
+  pub fn optional_modifiers(mut self, modifiers: &[ModifierKey]) -> Self {
+    self
+      .optional_modifiers
+      .extend_from_slice(modifiers);
+
+    self
+  }
+
+  /// Match the key regardless of the modifiers being pressed, other than the required ones.
+  #[must_use]
+  pub fn any_modifiers(self) -> Self {
+    use ModifierKey::{AltKey, CtrlKey, MetaKey, ShiftKey};
+    self.optional_modifiers(&[CtrlKey, ShiftKey, AltKey, MetaKey])
+  }
+
   /// Add multiple modifiers to the shortcut.
   #[must_use]
   pub fn modifiers(mut self, modifiers: &[ModifierKey]) -> Self {
@@ -139,6 +530,13 @@ impl KeyboardShortcutBuilder {
     self
   }
 
+  /// Add the `CmdOrCtrl` modifier to the shortcut.
+  #[must_use]
+  pub fn cmd_or_ctrl_key(mut self) -> Self {
+    self.modifiers.push(ModifierKey::CmdOrCtrl);
+    self
+  }
+
   /// Add the `CtrlKey` modifier to the shortcut.
   #[must_use]
   pub fn ctrl_key(mut self) -> Self {
@@ -160,11 +558,203 @@ impl KeyboardShortcutBuilder {
     self
   }
 
+  /// Like [`Builder::skip_editable`](crate::Builder::skip_editable), but only for this shortcut.
+  #[must_use]
+  pub fn skip_editable(mut self) -> Self {
+    self.skip_editable = true;
+    self
+  }
+
+  /// Like [`Builder::skip_selector`](crate::Builder::skip_selector), but only for this shortcut.
+  #[must_use]
+  pub fn skip_selector(mut self, selector: impl AsRef<str>) -> Self {
+    self
+      .skip_selectors
+      .push(selector.as_ref().to_owned());
+
+    self
+  }
+
+  /// Like [`Builder::listener_options`](crate::Builder::listener_options), but only for this shortcut.
+  #[must_use]
+  pub fn listener_options(mut self, options: ListenerOptions) -> Self {
+    self.listener_options = Some(options);
+    self
+  }
+
+  /// Prevent these events instead of only `keydown`.
+  ///
+  /// # Examples
+  /// ```
+  /// use tauri_plugin_prevent_default::{KeyRepeat, KeyboardEvent, KeyboardShortcut};
+  ///
+  /// // Holding the space bar should not scroll the page, but the first press should still work.
+  /// let shortcut = KeyboardShortcut::builder(" ")
+  ///   .events(&[KeyboardEvent::KeyDown, KeyboardEvent::KeyPress])
+  ///   .repeat(KeyRepeat::Only)
+  ///   .build();
+  /// ```
+  #[must_use]
+  pub fn events(mut self, events: &[KeyboardEvent]) -> Self {
+    self.events = events.to_vec();
+    self
+  }
+
+  /// Choose which presses of the key are prevented when it is held down.
+  #[must_use]
+  pub fn repeat(mut self, repeat: KeyRepeat) -> Self {
+    self.repeat = repeat;
+    self
+  }
+
   /// Build the keyboard shortcut.
   pub fn build(self) -> KeyboardShortcut {
     KeyboardShortcut {
       key: self.key,
+      code: self.code,
       modifiers: self.modifiers,
+      optional_modifiers: self.optional_modifiers,
+      skip_editable: self.skip_editable,
+      skip_selectors: self.skip_selectors,
+      listener_options: self.listener_options,
+      events: self.events,
+      repeat: self.repeat,
+    }
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::{Key, KeyRepeat, KeyboardEvent, KeyboardShortcut};
+  use crate::error::Error;
+  use crate::shortcut::ModifierKey::{AltKey, CtrlKey, MetaKey, ShiftKey};
+
+  fn parse(shortcut: &str) -> KeyboardShortcut {
+    shortcut.parse().unwrap()
+  }
+
+  #[test]
+  fn keyboard_from_str() {
+    let shortcut = parse("Ctrl+Shift+I");
+    assert_eq!(shortcut.key(), Some(&Key::I));
+    assert_eq!(shortcut.modifiers(), &[CtrlKey, ShiftKey]);
+
+    let shortcut = parse("shift+F5");
+    assert_eq!(shortcut.key(), Some(&Key::F5));
+    assert_eq!(shortcut.modifiers(), &[ShiftKey]);
+
+    let shortcut = parse("F7");
+    assert_eq!(shortcut.key(), Some(&Key::F7));
+    assert!(shortcut.modifiers().is_empty());
+
+    let shortcut = parse("Cmd + Option + Tab");
+    assert_eq!(shortcut.key(), Some(&Key::Tab));
+    assert_eq!(shortcut.modifiers(), &[MetaKey, AltKey]);
+
+    let shortcut = parse("Ctrl++");
+    assert_eq!(shortcut.key(), Some(&Key::Plus));
+    assert_eq!(shortcut.modifiers(), &[CtrlKey]);
+
+    let shortcut = parse("Ctrl+Shift?+F5");
+    assert_eq!(shortcut.modifiers(), &[CtrlKey]);
+    assert_eq!(shortcut.optional_modifiers(), &[ShiftKey]);
+
+    let shortcut = parse("*+F5");
+    assert!(shortcut.modifiers().is_empty());
+    assert_eq!(shortcut, KeyboardShortcut::any_modifiers("F5"));
+
+    let shortcut = parse("Ctrl+code:KeyF");
+    assert_eq!(shortcut.key(), None);
+    assert_eq!(shortcut.code(), Some("KeyF"));
+
+    let shortcut = parse("Shift+1|code:Digit1");
+    assert_eq!(shortcut.key(), Some(&Key::Digit1));
+    assert_eq!(shortcut.code(), Some("Digit1"));
+    assert_eq!(shortcut.modifiers(), &[ShiftKey]);
+  }
+
+  #[test]
+  fn keyboard_display_round_trip() {
+    for shortcut in [
+      "Ctrl+Shift+I",
+      "ctrl+shift+i",
+      "Shift+F5",
+      "Meta+Alt+Ctrl+A",
+      "F3",
+      "Ctrl+code:KeyF",
+      "Ctrl+F|code:KeyF",
+      "Ctrl+Alt?+F5",
+      "*+F5",
+      "Ctrl+Space",
+      "Space",
+    ] {
+      let display = parse(shortcut).to_string();
+      assert_eq!(parse(&display).to_string(), display);
+    }
+
+    assert_eq!(parse("Ctrl+Shift+I").to_string(), "keyboard:ctrl+shift+i");
+    assert_eq!(
+      KeyboardShortcut::with_ctrl(Key::Space).to_string(),
+      "keyboard:ctrl+space"
+    );
+    assert_eq!(
+      parse("keyboard:ctrl+shift+i").to_string(),
+      "keyboard:ctrl+shift+i"
+    );
+  }
+
+  #[test]
+  fn keyboard_events() {
+    let shortcut = KeyboardShortcut::builder(" ")
+      .events(&[KeyboardEvent::KeyDown, KeyboardEvent::KeyPress])
+      .repeat(KeyRepeat::Only)
+      .build();
+
+    let json = serde_json::to_string(&shortcut).unwrap();
+    assert!(json.ends_with(r#""events":["keydown","keypress"],"repeat":"only"}"#));
+    assert_eq!(
+      serde_json::from_str::<KeyboardShortcut>(&json).unwrap(),
+      shortcut
+    );
+
+    let shortcut = serde_json::from_str::<KeyboardShortcut>(r#"{ "key": "F5" }"#).unwrap();
+    assert_eq!(shortcut.events(), [KeyboardEvent::KeyDown]);
+    assert_eq!(shortcut.repeat(), KeyRepeat::Any);
+    assert_eq!(shortcut, KeyboardShortcut::new("F5"));
+
+    let shortcut = KeyboardShortcut::builder("a")
+      .events(&[KeyboardEvent::KeyUp])
+      .repeat(KeyRepeat::Only)
+      .build();
+    assert!(matches!(
+      shortcut.validate(),
+      Err(Error::ContradictoryShortcut { .. })
+    ));
+
+    let shortcut = KeyboardShortcut::builder("a").events(&[]).build();
+    assert!(matches!(
+      shortcut.validate(),
+      Err(Error::ContradictoryShortcut { .. })
+    ));
+  }
+
+  #[test]
+  fn invalid_keyboard_shortcut() {
+    for shortcut in [
+      "",
+      "  ",
+      "Ctrl+",
+      "Hyper+A",
+      "keyboard:",
+      "Ctrl++A",
+      "Ctrl+code:",
+      "Ctrl+|code:KeyF",
+    ] {
+      let result = shortcut.parse::<KeyboardShortcut>();
+      assert!(
+        matches!(result, Err(Error::InvalidKeyboardShortcut { .. })),
+        "{shortcut}"
+      );
     }
   }
 }
@@ -1,13 +1,27 @@
+mod key;
 mod keyboard;
 mod pointer;
+mod sequence;
+mod values;
 
+use crate::error::Error;
+use crate::listener::ListenerOptions;
+use crate::platform::Platform;
+use serde::de::{self, Deserializer};
+use serde::{Deserialize, Serialize, Serializer};
+use std::collections::HashMap;
 use std::fmt;
-use strum::{Display, EnumIs};
+use std::str::FromStr;
+use strum::{Display, EnumIs, EnumString};
 
-pub use keyboard::{KeyboardShortcut, KeyboardShortcutBuilder};
+pub use key::Key;
+pub use keyboard::{KeyRepeat, KeyboardEvent, KeyboardShortcut, KeyboardShortcutBuilder};
 pub use pointer::{PointerEvent, PointerShortcut, PointerShortcutBuilder};
+pub use sequence::{SequenceShortcut, SequenceShortcutBuilder};
 
-pub trait Shortcut: fmt::Display {
+pub(crate) use keyboard::is_keydown_only;
+
+pub trait Shortcut: fmt::Display + Send + Sync {
   fn kind(&self) -> ShortcutKind<'_>;
 }
 
@@ -23,10 +37,17 @@ impl Shortcut for PointerShortcut {
   }
 }
 
+impl Shortcut for SequenceShortcut {
+  fn kind(&self) -> ShortcutKind<'_> {
+    ShortcutKind::Sequence(self)
+  }
+}
+
 #[derive(Debug)]
 pub enum ShortcutKind<'a> {
   Keyboard(&'a KeyboardShortcut),
   Pointer(&'a PointerShortcut),
+  Sequence(&'a SequenceShortcut),
 }
 
 impl ShortcutKind<'_> {
@@ -39,29 +60,156 @@ impl ShortcutKind<'_> {
   pub fn is_pointer(&self) -> bool {
     matches!(self, ShortcutKind::Pointer(_))
   }
+
+  /// Returns `true` if the shortcut is a sequence of keyboard shortcuts.
+  pub fn is_sequence(&self) -> bool {
+    matches!(self, ShortcutKind::Sequence(_))
+  }
+}
+
+/// Check the shortcuts for mistakes that would otherwise go unnoticed,
+/// such as misspelled keys or the same shortcut being added twice.
+pub(crate) fn validate(shortcuts: &[Box<dyn Shortcut>]) -> crate::Result<()> {
+  let mut seen = HashMap::with_capacity(shortcuts.len());
+  for shortcut in shortcuts {
+    let kind = shortcut.kind();
+    match kind {
+      ShortcutKind::Keyboard(it) => it.validate()?,
+      ShortcutKind::Sequence(it) => it.validate()?,
+      ShortcutKind::Pointer(_) => {}
+    }
+
+    let display = shortcut.to_string();
+    match seen.get(&display) {
+      Some(other) if *other == options(&kind) => {
+        return Err(Error::DuplicateShortcut { shortcut: display });
+      }
+      Some(_) => {
+        return Err(Error::ContradictoryShortcut {
+          shortcut: display,
+          reason: String::from("added again with different exemptions, listener options or events"),
+        });
+      }
+      None => {
+        seen.insert(display, options(&kind));
+      }
+    }
+  }
+
+  Ok(())
+}
+
+/// Whether both shortcuts are the same, as [`validate`] would consider them duplicates.
+pub(crate) fn is_same(a: &dyn Shortcut, b: &dyn Shortcut) -> bool {
+  a.to_string() == b.to_string() && options(&a.kind()) == options(&b.kind())
+}
+
+/// Everything about a shortcut that is not part of how it is displayed.
+#[derive(PartialEq)]
+struct Options<'a> {
+  skip_editable: bool,
+  skip_selectors: &'a [String],
+  listener: Option<ListenerOptions>,
+  events: &'a [KeyboardEvent],
+  repeat: KeyRepeat,
+}
+
+fn options<'a>(kind: &ShortcutKind<'a>) -> Options<'a> {
+  match kind {
+    ShortcutKind::Keyboard(it) => Options {
+      skip_editable: it.skips_editable(),
+      skip_selectors: it.skip_selectors(),
+      listener: it.listener_options(),
+      events: it.events(),
+      repeat: it.repeat(),
+    },
+    ShortcutKind::Pointer(it) => Options {
+      skip_editable: it.skips_editable(),
+      skip_selectors: it.skip_selectors(),
+      listener: it.listener_options(),
+      events: &[],
+      repeat: KeyRepeat::default(),
+    },
+    ShortcutKind::Sequence(it) => Options {
+      skip_editable: it.skips_editable(),
+      skip_selectors: it.skip_selectors(),
+      listener: it.listener_options(),
+      events: &[],
+      repeat: KeyRepeat::default(),
+    },
+  }
 }
 
 #[non_exhaustive]
-#[derive(Clone, Copy, Debug, Display, PartialEq, Eq, Hash, EnumIs)]
-#[strum(serialize_all = "camelCase")]
+#[derive(Clone, Copy, Debug, Display, PartialEq, Eq, Hash, EnumIs, EnumString)]
+#[strum(serialize_all = "camelCase", ascii_case_insensitive)]
 pub enum ModifierKey {
+  #[strum(to_string = "altKey", serialize = "alt", serialize = "option")]
   AltKey,
+  #[strum(to_string = "ctrlKey", serialize = "ctrl", serialize = "control")]
   CtrlKey,
+  #[strum(
+    to_string = "metaKey",
+    serialize = "meta",
+    serialize = "cmd",
+    serialize = "command",
+    serialize = "super"
+  )]
   MetaKey,
+  #[strum(to_string = "shiftKey", serialize = "shift")]
   ShiftKey,
+  /// `MetaKey` on Apple platforms and `CtrlKey` elsewhere.
+  #[strum(
+    to_string = "cmdOrCtrl",
+    serialize = "commandOrControl",
+    serialize = "cmdOrControl",
+    serialize = "commandOrCtrl",
+    serialize = "primary"
+  )]
+  CmdOrCtrl,
 }
 
 impl ModifierKey {
+  /// The modifier that should actually be pressed on the given platform.
+  #[must_use]
+  pub fn resolve(self, platform: Platform) -> Self {
+    match self {
+      ModifierKey::CmdOrCtrl if platform.is_apple() => ModifierKey::MetaKey,
+      ModifierKey::CmdOrCtrl => ModifierKey::CtrlKey,
+      _ => self,
+    }
+  }
+
   fn precedence(self) -> u8 {
     match self {
-      ModifierKey::CtrlKey => 0,
-      ModifierKey::ShiftKey => 1,
-      ModifierKey::AltKey => 2,
-      ModifierKey::MetaKey => 3,
+      ModifierKey::CmdOrCtrl => 0,
+      ModifierKey::CtrlKey => 1,
+      ModifierKey::ShiftKey => 2,
+      ModifierKey::AltKey => 3,
+      ModifierKey::MetaKey => 4,
     }
   }
 }
 
+impl Serialize for ModifierKey {
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    serializer.serialize_str(&self.to_string())
+  }
+}
+
+impl<'de> Deserialize<'de> for ModifierKey {
+  fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+  where
+    D: Deserializer<'de>,
+  {
+    let value = String::deserialize(deserializer)?;
+    ModifierKey::from_str(&value).map_err(de::Error::custom)
+  }
+}
+
 impl PartialOrd for ModifierKey {
   fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
     Some(self.cmp(other))
@@ -76,7 +224,7 @@ impl Ord for ModifierKey {
 
 #[cfg(test)]
 mod test {
-  use super::ModifierKey::{AltKey, CtrlKey, MetaKey, ShiftKey};
+  use super::ModifierKey::{AltKey, CmdOrCtrl, CtrlKey, MetaKey, ShiftKey};
   use super::*;
 
   #[test]
@@ -90,6 +238,56 @@ mod test {
     let pointer = PointerShortcut::new(PointerEvent::ContextMenu);
     let pointer = Box::new(pointer) as Box<dyn Shortcut>;
     assert!(pointer.kind().is_pointer());
+
+    // Sequence
+    let sequence = SequenceShortcut::new([KeyboardShortcut::with_ctrl("K")]);
+    let sequence = Box::new(sequence) as Box<dyn Shortcut>;
+    assert!(sequence.kind().is_sequence());
+  }
+
+  #[test]
+  fn validate() {
+    let validate = |shortcuts: Vec<Box<dyn Shortcut>>| super::validate(&shortcuts);
+
+    assert!(validate(vec![
+      Box::new(KeyboardShortcut::with_ctrl("F")),
+      Box::new(KeyboardShortcut::with_code("KeyF")),
+      Box::new(KeyboardShortcut::new("Enter")),
+      Box::new(PointerShortcut::new(PointerEvent::ContextMenu)),
+    ])
+    .is_ok());
+
+    let result = validate(vec![Box::new(KeyboardShortcut::with_ctrl("Contol"))]);
+    assert!(matches!(result, Err(Error::UnknownKey { key, .. }) if key == "Contol"));
+
+    let result = validate(vec![Box::new(KeyboardShortcut::with_code("KeyAB"))]);
+    assert!(matches!(result, Err(Error::UnknownCode { code, .. }) if code == "KeyAB"));
+
+    let result = validate(vec![Box::new(KeyboardShortcut::new("  "))]);
+    assert!(matches!(result, Err(Error::EmptyKey { .. })));
+
+    let result = validate(vec![
+      Box::new(KeyboardShortcut::with_ctrl_shift("I")),
+      Box::new(
+        KeyboardShortcut::builder("I")
+          .shift_key()
+          .ctrl_key()
+          .build(),
+      ),
+    ]);
+    assert!(
+      matches!(result, Err(Error::DuplicateShortcut { shortcut }) if shortcut == "keyboard:ctrl+shift+i")
+    );
+
+    let result = validate(vec![
+      Box::new(PointerShortcut::new(PointerEvent::ContextMenu)),
+      Box::new(
+        PointerShortcut::builder(PointerEvent::ContextMenu)
+          .skip_editable()
+          .build(),
+      ),
+    ]);
+    assert!(matches!(result, Err(Error::ContradictoryShortcut { .. })));
   }
 
   #[test]
@@ -103,4 +301,33 @@ mod test {
 
     assert_eq!(modifiers, vec![CtrlKey, ShiftKey, AltKey, MetaKey]);
   }
+
+  #[test]
+  fn modifier_key_resolve() {
+    assert_eq!(CmdOrCtrl.resolve(Platform::MacOs), MetaKey);
+    assert_eq!(CmdOrCtrl.resolve(Platform::Ios), MetaKey);
+    assert_eq!(CmdOrCtrl.resolve(Platform::Windows), CtrlKey);
+    assert_eq!(CmdOrCtrl.resolve(Platform::Linux), CtrlKey);
+    assert_eq!(AltKey.resolve(Platform::MacOs), AltKey);
+  }
+
+  #[test]
+  fn modifier_key_from_str() {
+    assert_eq!("ctrl".parse::<ModifierKey>().unwrap(), CtrlKey);
+    assert_eq!("Control".parse::<ModifierKey>().unwrap(), CtrlKey);
+    assert_eq!("ctrlKey".parse::<ModifierKey>().unwrap(), CtrlKey);
+    assert_eq!("SHIFT".parse::<ModifierKey>().unwrap(), ShiftKey);
+    assert_eq!("Option".parse::<ModifierKey>().unwrap(), AltKey);
+    assert_eq!("cmd".parse::<ModifierKey>().unwrap(), MetaKey);
+    assert_eq!("CmdOrCtrl".parse::<ModifierKey>().unwrap(), CmdOrCtrl);
+    assert_eq!(
+      "CommandOrControl".parse::<ModifierKey>().unwrap(),
+      CmdOrCtrl
+    );
+    assert!("hyper".parse::<ModifierKey>().is_err());
+
+    // Display must keep matching the `KeyboardEvent` properties.
+    assert_eq!(CtrlKey.to_string(), "ctrlKey");
+    assert_eq!(MetaKey.to_string(), "metaKey");
+  }
 }
@@ -1,22 +1,68 @@
 use crate::display;
+use crate::error::Error;
+use crate::listener::ListenerOptions;
+use serde::{Deserialize, Serialize};
 use std::fmt;
+use std::str::FromStr;
 use strum::{Display as EnumDisplay, EnumIs, EnumString};
 
 #[non_exhaustive]
-#[derive(Clone, Copy, Debug, PartialEq, Eq, EnumDisplay, EnumIs, EnumString)]
+#[derive(
+  Clone, Copy, Debug, PartialEq, Eq, EnumDisplay, EnumIs, EnumString, Deserialize, Serialize,
+)]
+#[serde(rename_all = "lowercase")]
 #[strum(serialize_all = "lowercase")]
 pub enum PointerEvent {
+  /// Context menu (right click).
   ContextMenu,
+  /// Middle click.
+  AuxClick,
+  /// Double click.
+  DblClick,
+  /// Start of a drag operation, such as dragging an image or a link.
+  DragStart,
+  /// Start of a text selection.
+  SelectStart,
+  /// Drop of a dragged item, such as a file.
+  Drop,
+  /// Dragging an item over the page.
+  DragOver,
+  /// Mouse back button (`button === 3`).
+  MouseBack,
+  /// Mouse forward button (`button === 4`).
+  MouseForward,
+  /// Mouse wheel while holding `Ctrl`, which is also how most trackpads report a pinch.
+  ZoomWheel,
+  /// Pinch gesture on engines that support `gesturestart`, such as WebKit.
+  ZoomGesture,
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct PointerShortcut {
   event: PointerEvent,
+  #[serde(
+    default,
+    rename = "skipEditable",
+    skip_serializing_if = "crate::util::is_false"
+  )]
+  skip_editable: bool,
+  #[serde(
+    default,
+    rename = "skipSelectors",
+    skip_serializing_if = "Vec::is_empty"
+  )]
+  skip_selectors: Vec<String>,
+  #[serde(
+    default,
+    rename = "listenerOptions",
+    skip_serializing_if = "Option::is_none"
+  )]
+  listener_options: Option<ListenerOptions>,
 }
 
 impl PointerShortcut {
   pub fn new(event: PointerEvent) -> Self {
-    Self { event }
+    Self::builder(event).build()
   }
 
   /// Initialize a new pointer shortcut builder with the specified event.
@@ -28,6 +74,21 @@ impl PointerShortcut {
   pub fn event(&self) -> PointerEvent {
     self.event
   }
+
+  /// Whether the shortcut is allowed on editable elements.
+  pub fn skips_editable(&self) -> bool {
+    self.skip_editable
+  }
+
+  /// CSS selectors of the elements on which the shortcut is allowed.
+  pub fn skip_selectors(&self) -> &[String] {
+    &self.skip_selectors
+  }
+
+  /// Listener options that override those of the [`Builder`](crate::Builder) for this shortcut.
+  pub fn listener_options(&self) -> Option<ListenerOptions> {
+    self.listener_options
+  }
 }
 
 impl fmt::Display for PointerShortcut {
@@ -36,18 +97,75 @@ impl fmt::Display for PointerShortcut {
   }
 }
 
+impl FromStr for PointerShortcut {
+  type Err = Error;
+
+  /// Parse a shortcut such as `pointer:contextmenu`.
+  fn from_str(value: &str) -> Result<Self, Self::Err> {
+    let value = value.trim();
+    let event = value.strip_prefix("pointer:").unwrap_or(value);
+    Ok(Self::new(PointerEvent::from_str(event)?))
+  }
+}
+
 #[derive(Debug)]
 pub struct PointerShortcutBuilder {
   event: PointerEvent,
+  skip_editable: bool,
+  skip_selectors: Vec<String>,
+  listener_options: Option<ListenerOptions>,
 }
 
 impl PointerShortcutBuilder {
   pub fn new(event: PointerEvent) -> Self {
-    Self { event }
+    Self {
+      event,
+      skip_editable: false,
+      skip_selectors: Vec::new(),
+      listener_options: None,
+    }
+  }
+
+  /// Like [`Builder::skip_editable`](crate::Builder::skip_editable), but only for this shortcut.
+  #[must_use]
+  pub fn skip_editable(mut self) -> Self {
+    self.skip_editable = true;
+    self
+  }
+
+  /// Like [`Builder::skip_selector`](crate::Builder::skip_selector), but only for this shortcut.
+  ///
+  /// # Examples
+  /// ```
+  /// use tauri_plugin_prevent_default::{PointerEvent, PointerShortcut};
+  ///
+  /// let shortcut = PointerShortcut::builder(PointerEvent::ContextMenu)
+  ///   .skip_selector(".allow-native-menu")
+  ///   .build();
+  /// ```
+  #[must_use]
+  pub fn skip_selector(mut self, selector: impl AsRef<str>) -> Self {
+    self
+      .skip_selectors
+      .push(selector.as_ref().to_owned());
+
+    self
+  }
+
+  /// Like [`Builder::listener_options`](crate::Builder::listener_options), but only for this shortcut.
+  #[must_use]
+  pub fn listener_options(mut self, options: ListenerOptions) -> Self {
+    self.listener_options = Some(options);
+    self
   }
 
   /// Build the pointer shortcut.
   pub fn build(self) -> PointerShortcut {
-    PointerShortcut { event: self.event }
+    PointerShortcut {
+      event: self.event,
+      skip_editable: self.skip_editable,
+      skip_selectors: self.skip_selectors,
+      listener_options: self.listener_options,
+    }
   }
 }
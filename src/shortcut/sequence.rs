@@ -0,0 +1,364 @@
+use super::KeyboardShortcut;
+use crate::display;
+use crate::error::Error;
+use crate::listener::ListenerOptions;
+use serde::{Deserialize, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Sequence of keyboard shortcuts that must be pressed one after the other,
+/// such as `Ctrl+K` followed by `Ctrl+S`.
+///
+/// Only the last step is prevented by default. The exemptions and listener options
+/// of the steps themselves are ignored in favor of those of the sequence.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(try_from = "RawSequenceShortcut")]
+pub struct SequenceShortcut {
+  steps: Vec<KeyboardShortcut>,
+  #[serde(serialize_with = "serialize_millis")]
+  timeout: Duration,
+  #[serde(
+    rename = "preventAllSteps",
+    skip_serializing_if = "crate::util::is_false"
+  )]
+  prevent_all_steps: bool,
+  #[serde(rename = "skipEditable", skip_serializing_if = "crate::util::is_false")]
+  skip_editable: bool,
+  #[serde(rename = "skipSelectors", skip_serializing_if = "Vec::is_empty")]
+  skip_selectors: Vec<String>,
+  #[serde(rename = "listenerOptions", skip_serializing_if = "Option::is_none")]
+  listener_options: Option<ListenerOptions>,
+}
+
+impl SequenceShortcut {
+  /// Create a new sequence with the given steps.
+  ///
+  /// # Examples
+  /// ```
+  /// use tauri_plugin_prevent_default::{KeyboardShortcut, SequenceShortcut};
+  ///
+  /// tauri_plugin_prevent_default::Builder::new()
+  ///   .shortcut(SequenceShortcut::new([
+  ///     KeyboardShortcut::with_ctrl("k"),
+  ///     KeyboardShortcut::with_ctrl("s"),
+  ///   ]))
+  ///   .build();
+  /// ```
+  pub fn new(steps: impl IntoIterator<Item = KeyboardShortcut>) -> Self {
+    Self::builder(steps).build()
+  }
+
+  /// Initialize a new sequence shortcut builder with the given steps.
+  pub fn builder(steps: impl IntoIterator<Item = KeyboardShortcut>) -> SequenceShortcutBuilder {
+    SequenceShortcutBuilder::new(steps)
+  }
+
+  /// Steps of the sequence, in the order they must be pressed.
+  pub fn steps(&self) -> &[KeyboardShortcut] {
+    &self.steps
+  }
+
+  /// Maximum time between two steps.
+  pub fn timeout(&self) -> Duration {
+    self.timeout
+  }
+
+  /// Whether every step is prevented, instead of only the last one.
+  pub fn prevents_all_steps(&self) -> bool {
+    self.prevent_all_steps
+  }
+
+  /// Whether the shortcut is allowed on editable elements.
+  pub fn skips_editable(&self) -> bool {
+    self.skip_editable
+  }
+
+  /// CSS selectors of the elements on which the shortcut is allowed.
+  pub fn skip_selectors(&self) -> &[String] {
+    &self.skip_selectors
+  }
+
+  /// Listener options that override those of the [`Builder`](crate::Builder) for this shortcut.
+  pub fn listener_options(&self) -> Option<ListenerOptions> {
+    self.listener_options
+  }
+
+  /// Check that the sequence has at least two steps, and that each of them is valid.
+  pub(crate) fn validate(&self) -> Result<(), Error> {
+    if self.steps.len() < 2 {
+      return Err(Error::InvalidSequenceShortcut {
+        shortcut: self.to_string(),
+        reason: String::from("at least two steps are required"),
+      });
+    }
+
+    for step in &self.steps {
+      step.validate()?;
+    }
+
+    Ok(())
+  }
+}
+
+fn serialize_millis<S>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error>
+where
+  S: Serializer,
+{
+  let millis = u64::try_from(duration.as_millis()).unwrap_or(u64::MAX);
+  serializer.serialize_u64(millis)
+}
+
+impl fmt::Display for SequenceShortcut {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", display::sequence(&self.steps))
+  }
+}
+
+#[derive(Deserialize)]
+struct RawSequenceShortcut {
+  steps: Vec<RawStep>,
+  /// Milliseconds.
+  #[serde(default)]
+  timeout: Option<u64>,
+  #[serde(default, rename = "preventAllSteps")]
+  prevent_all_steps: bool,
+  #[serde(default, rename = "skipEditable")]
+  skip_editable: bool,
+  #[serde(default, rename = "skipSelectors")]
+  skip_selectors: Vec<String>,
+  #[serde(default, rename = "listenerOptions")]
+  listener_options: Option<ListenerOptions>,
+}
+
+/// Steps can also be written as strings, such as `Ctrl+K`.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawStep {
+  String(String),
+  Keyboard(KeyboardShortcut),
+}
+
+impl TryFrom<RawSequenceShortcut> for SequenceShortcut {
+  type Error = Error;
+
+  fn try_from(raw: RawSequenceShortcut) -> Result<Self, Self::Error> {
+    if raw.steps.is_empty() {
+      return Err(Error::InvalidSequenceShortcut {
+        shortcut: display::sequence(&[]),
+        reason: String::from("missing steps"),
+      });
+    }
+
+    let steps = raw
+      .steps
+      .into_iter()
+      .map(|it| match it {
+        RawStep::String(it) => it.parse(),
+        RawStep::Keyboard(it) => Ok(it),
+      })
+      .collect::<Result<_, _>>()?;
+
+    Ok(Self {
+      steps,
+      timeout: raw
+        .timeout
+        .map_or(DEFAULT_TIMEOUT, Duration::from_millis),
+      prevent_all_steps: raw.prevent_all_steps,
+      skip_editable: raw.skip_editable,
+      skip_selectors: raw.skip_selectors,
+      listener_options: raw.listener_options,
+    })
+  }
+}
+
+impl FromStr for SequenceShortcut {
+  type Err = Error;
+
+  /// Parse a sequence such as `Ctrl+K Ctrl+S` or `sequence:ctrl+k ctrl+s`.
+  ///
+  /// Steps are separated by whitespace, and each of them is parsed as a [`KeyboardShortcut`].
+  fn from_str(value: &str) -> Result<Self, Self::Err> {
+    let sequence = value.trim();
+    let sequence = sequence
+      .get(..9)
+      .filter(|prefix| prefix.eq_ignore_ascii_case("sequence:"))
+      .map_or(sequence, |_| &sequence[9..]);
+
+    let steps = sequence
+      .split_whitespace()
+      .map(KeyboardShortcut::from_str)
+      .collect::<Result<Vec<_>, _>>()?;
+
+    if steps.is_empty() {
+      return Err(Error::InvalidSequenceShortcut {
+        shortcut: value.to_owned(),
+        reason: String::from("missing steps"),
+      });
+    }
+
+    Ok(Self::new(steps))
+  }
+}
+
+#[derive(Debug)]
+pub struct SequenceShortcutBuilder {
+  steps: Vec<KeyboardShortcut>,
+  timeout: Duration,
+  prevent_all_steps: bool,
+  skip_editable: bool,
+  skip_selectors: Vec<String>,
+  listener_options: Option<ListenerOptions>,
+}
+
+impl SequenceShortcutBuilder {
+  pub fn new(steps: impl IntoIterator<Item = KeyboardShortcut>) -> Self {
+    Self {
+      steps: steps.into_iter().collect(),
+      timeout: DEFAULT_TIMEOUT,
+      prevent_all_steps: false,
+      skip_editable: false,
+      skip_selectors: Vec::new(),
+      listener_options: None,
+    }
+  }
+
+  /// Add a step to the end of the sequence.
+  #[must_use]
+  pub fn step(mut self, step: KeyboardShortcut) -> Self {
+    self.steps.push(step);
+    self
+  }
+
+  /// Maximum time between two steps, after which the sequence starts over.
+  /// Defaults to one second.
+  ///
+  /// # Examples
+  /// ```
+  /// use std::time::Duration;
+  /// use tauri_plugin_prevent_default::{KeyboardShortcut, SequenceShortcut};
+  ///
+  /// let shortcut = SequenceShortcut::builder([KeyboardShortcut::with_ctrl("k")])
+  ///   .step(KeyboardShortcut::with_ctrl("s"))
+  ///   .timeout(Duration::from_millis(500))
+  ///   .build();
+  /// ```
+  #[must_use]
+  pub fn timeout(mut self, timeout: Duration) -> Self {
+    self.timeout = timeout;
+    self
+  }
+
+  /// Prevent every step of the sequence, instead of only the last one.
+  #[must_use]
+  pub fn prevent_all_steps(mut self) -> Self {
+    self.prevent_all_steps = true;
+    self
+  }
+
+  /// Like [`Builder::skip_editable`](crate::Builder::skip_editable), but only for this shortcut.
+  #[must_use]
+  pub fn skip_editable(mut self) -> Self {
+    self.skip_editable = true;
+    self
+  }
+
+  /// Like [`Builder::skip_selector`](crate::Builder::skip_selector), but only for this shortcut.
+  #[must_use]
+  pub fn skip_selector(mut self, selector: impl AsRef<str>) -> Self {
+    self
+      .skip_selectors
+      .push(selector.as_ref().to_owned());
+
+    self
+  }
+
+  /// Like [`Builder::listener_options`](crate::Builder::listener_options), but only for this shortcut.
+  #[must_use]
+  pub fn listener_options(mut self, options: ListenerOptions) -> Self {
+    self.listener_options = Some(options);
+    self
+  }
+
+  /// Build the sequence shortcut.
+  pub fn build(self) -> SequenceShortcut {
+    SequenceShortcut {
+      steps: self.steps,
+      timeout: self.timeout,
+      prevent_all_steps: self.prevent_all_steps,
+      skip_editable: self.skip_editable,
+      skip_selectors: self.skip_selectors,
+      listener_options: self.listener_options,
+    }
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::SequenceShortcut;
+  use crate::error::Error;
+  use crate::shortcut::{Key, KeyboardShortcut};
+  use std::time::Duration;
+
+  #[test]
+  fn sequence_from_str() {
+    let shortcut = "Ctrl+K Ctrl+S"
+      .parse::<SequenceShortcut>()
+      .unwrap();
+    assert_eq!(
+      shortcut.steps(),
+      [
+        KeyboardShortcut::with_ctrl("K"),
+        KeyboardShortcut::with_ctrl("S")
+      ]
+    );
+
+    assert_eq!(shortcut.to_string(), "sequence:ctrl+k ctrl+s");
+    assert_eq!(
+      shortcut
+        .to_string()
+        .parse::<SequenceShortcut>()
+        .unwrap(),
+      shortcut
+    );
+
+    let shortcut = SequenceShortcut::builder([KeyboardShortcut::with_ctrl("K")])
+      .step(KeyboardShortcut::new(Key::Space))
+      .build();
+
+    assert_eq!(shortcut.to_string(), "sequence:ctrl+k space");
+    assert_eq!(
+      shortcut
+        .to_string()
+        .parse::<SequenceShortcut>()
+        .unwrap(),
+      shortcut
+    );
+
+    for shortcut in ["", "sequence:", "Ctrl+K Hyper+S"] {
+      let result = shortcut.parse::<SequenceShortcut>();
+      assert!(result.is_err(), "{shortcut}");
+    }
+  }
+
+  #[test]
+  fn sequence_serde() {
+    let shortcut = SequenceShortcut::builder([KeyboardShortcut::with_ctrl("k")])
+      .step(KeyboardShortcut::new("g"))
+      .timeout(Duration::from_millis(500))
+      .prevent_all_steps()
+      .build();
+
+    let json = serde_json::to_string(&shortcut).unwrap();
+    assert!(json.contains(r#""timeout":500,"preventAllSteps":true"#));
+    assert_eq!(
+      serde_json::from_str::<SequenceShortcut>(&json).unwrap(),
+      shortcut
+    );
+
+    let result = SequenceShortcut::new([KeyboardShortcut::with_ctrl("k")]).validate();
+    assert!(matches!(result, Err(Error::InvalidSequenceShortcut { .. })));
+  }
+}
@@ -0,0 +1,186 @@
+//! Named values of `KeyboardEvent.key` and `KeyboardEvent.code`.
+//!
+//! <https://www.w3.org/TR/uievents-key/>
+//! <https://www.w3.org/TR/uievents-code/>
+
+#[rustfmt::skip]
+const KEY_VALUES: &[&str] = &[
+  // Special
+  "Unidentified",
+  // Modifier
+  "Alt", "AltGraph", "CapsLock", "Control", "Fn", "FnLock", "Hyper", "Meta", "NumLock",
+  "ScrollLock", "Shift", "Super", "Symbol", "SymbolLock",
+  // Whitespace
+  "Enter", "Tab",
+  // Navigation
+  "ArrowDown", "ArrowLeft", "ArrowRight", "ArrowUp", "End", "Home", "PageDown", "PageUp",
+  // Editing
+  "Backspace", "Clear", "Copy", "CrSel", "Cut", "Delete", "EraseEof", "ExSel", "Insert", "Paste",
+  "Redo", "Undo",
+  // UI
+  "Accept", "Again", "Attn", "Cancel", "ContextMenu", "Escape", "Execute", "Find", "Help",
+  "Pause", "Play", "Props", "Select", "ZoomIn", "ZoomOut",
+  // Device
+  "BrightnessDown", "BrightnessUp", "Eject", "Hibernate", "LogOff", "Power", "PowerOff",
+  "PrintScreen", "Standby", "WakeUp",
+  // IME and composition
+  "AllCandidates", "Alphanumeric", "CodeInput", "Compose", "Convert", "Dead", "FinalMode",
+  "GroupFirst", "GroupLast", "GroupNext", "GroupPrevious", "ModeChange", "NextCandidate",
+  "NonConvert", "PreviousCandidate", "Process", "SingleCandidate", "HangulMode", "HanjaMode",
+  "JunjaMode", "Eisu", "Hankaku", "Hiragana", "HiraganaKatakana", "KanaMode", "KanjiMode",
+  "Katakana", "Romaji", "Zenkaku", "ZenkakuHankaku",
+  // General-purpose function
+  "Soft1", "Soft2", "Soft3", "Soft4",
+  // Multimedia
+  "ChannelDown", "ChannelUp", "Close", "MailForward", "MailReply", "MailSend", "MediaClose",
+  "MediaFastForward", "MediaPause", "MediaPlay", "MediaPlayPause", "MediaRecord", "MediaRewind",
+  "MediaStop", "MediaTrackNext", "MediaTrackPrevious", "New", "Open", "Print", "Save",
+  "SpellCheck", "Key11", "Key12",
+  // Audio
+  "AudioBalanceLeft", "AudioBalanceRight", "AudioBassBoostDown", "AudioBassBoostToggle",
+  "AudioBassBoostUp", "AudioFaderFront", "AudioFaderRear", "AudioSurroundModeNext",
+  "AudioTrebleDown", "AudioTrebleUp", "AudioVolumeDown", "AudioVolumeUp", "AudioVolumeMute",
+  "MicrophoneToggle", "MicrophoneVolumeDown", "MicrophoneVolumeUp", "MicrophoneVolumeMute",
+  // Speech
+  "SpeechCorrectionList", "SpeechInputToggle",
+  // Application
+  "LaunchApplication1", "LaunchApplication2", "LaunchCalendar", "LaunchContacts", "LaunchMail",
+  "LaunchMediaPlayer", "LaunchMusicPlayer", "LaunchPhone", "LaunchScreenSaver",
+  "LaunchSpreadsheet", "LaunchWebBrowser", "LaunchWebCam", "LaunchWordProcessor",
+  // Browser
+  "BrowserBack", "BrowserFavorites", "BrowserForward", "BrowserHome", "BrowserRefresh",
+  "BrowserSearch", "BrowserStop",
+  // Mobile phone
+  "AppSwitch", "Call", "Camera", "CameraFocus", "EndCall", "GoBack", "GoHome", "HeadsetHook",
+  "LastNumberRedial", "Notification", "MannerMode", "VoiceDial",
+  // TV
+  "TV", "TV3DMode", "TVAntennaCable", "TVAudioDescription", "TVAudioDescriptionMixDown",
+  "TVAudioDescriptionMixUp", "TVContentsMenu", "TVDataService", "TVInput", "TVInputComponent1",
+  "TVInputComponent2", "TVInputComposite1", "TVInputComposite2", "TVInputHDMI1", "TVInputHDMI2",
+  "TVInputHDMI3", "TVInputHDMI4", "TVInputVGA1", "TVMediaContext", "TVNetwork", "TVNumberEntry",
+  "TVPower", "TVRadioService", "TVSatellite", "TVSatelliteBS", "TVSatelliteCS",
+  "TVSatelliteToggle", "TVTerrestrialAnalog", "TVTerrestrialDigital", "TVTimer",
+  // Media controller
+  "AVRInput", "AVRPower", "ColorF0Red", "ColorF1Green", "ColorF2Yellow", "ColorF3Blue",
+  "ColorF4Grey", "ColorF5Brown", "ClosedCaptionToggle", "Dimmer", "DisplaySwap", "DVR", "Exit",
+  "FavoriteClear0", "FavoriteClear1", "FavoriteClear2", "FavoriteClear3", "FavoriteRecall0",
+  "FavoriteRecall1", "FavoriteRecall2", "FavoriteRecall3", "FavoriteStore0", "FavoriteStore1",
+  "FavoriteStore2", "FavoriteStore3", "Guide", "GuideNextDay", "GuidePreviousDay", "Info",
+  "InstantReplay", "Link", "ListProgram", "LiveContent", "Lock", "MediaApps", "MediaAudioTrack",
+  "MediaLast", "MediaSkipBackward", "MediaSkipForward", "MediaStepBackward", "MediaStepForward",
+  "MediaTopMenu", "NavigateIn", "NavigateNext", "NavigateOut", "NavigatePrevious",
+  "NextFavoriteChannel", "NextUserProfile", "OnDemand", "Pairing", "PinPDown", "PinPMove",
+  "PinPToggle", "PinPUp", "PlaySpeedDown", "PlaySpeedReset", "PlaySpeedUp", "RandomToggle",
+  "RcLowBattery", "RecordSpeedNext", "RfBypass", "ScanChannelsToggle", "ScreenModeNext",
+  "Settings", "SplitScreenToggle", "STBInput", "STBPower", "Subtitle", "Teletext",
+  "VideoModeNext", "Wink", "ZoomToggle",
+];
+
+#[rustfmt::skip]
+const CODE_VALUES: &[&str] = &[
+  // Alphanumeric section
+  "Backquote", "Backslash", "BracketLeft", "BracketRight", "Comma", "Equal", "IntlBackslash",
+  "IntlRo", "IntlYen", "Minus", "Period", "Quote", "Semicolon", "Slash",
+  // Functional keys
+  "AltLeft", "AltRight", "Backspace", "CapsLock", "ContextMenu", "ControlLeft", "ControlRight",
+  "Enter", "MetaLeft", "MetaRight", "ShiftLeft", "ShiftRight", "Space", "Tab", "Convert",
+  "KanaMode", "Lang1", "Lang2", "Lang3", "Lang4", "Lang5", "NonConvert",
+  // Control pad
+  "Delete", "End", "Help", "Home", "Insert", "PageDown", "PageUp",
+  // Arrow pad
+  "ArrowDown", "ArrowLeft", "ArrowRight", "ArrowUp",
+  // Numpad
+  "NumLock", "NumpadAdd", "NumpadBackspace", "NumpadClear", "NumpadClearEntry", "NumpadComma",
+  "NumpadDecimal", "NumpadDivide", "NumpadEnter", "NumpadEqual", "NumpadHash", "NumpadMemoryAdd",
+  "NumpadMemoryClear", "NumpadMemoryRecall", "NumpadMemoryStore", "NumpadMemorySubtract",
+  "NumpadMultiply", "NumpadParenLeft", "NumpadParenRight", "NumpadStar", "NumpadSubtract",
+  // Function section
+  "Escape", "Fn", "FnLock", "PrintScreen", "ScrollLock", "Pause",
+  // Media keys
+  "BrowserBack", "BrowserFavorites", "BrowserForward", "BrowserHome", "BrowserRefresh",
+  "BrowserSearch", "BrowserStop", "Eject", "LaunchApp1", "LaunchApp2", "LaunchMail",
+  "MediaPlayPause", "MediaSelect", "MediaStop", "MediaTrackNext", "MediaTrackPrevious", "Power",
+  "Sleep", "AudioVolumeDown", "AudioVolumeMute", "AudioVolumeUp", "WakeUp",
+  // Legacy, non-standard and special keys
+  "Hyper", "Super", "Turbo", "Abort", "Resume", "Suspend", "Again", "Copy", "Cut", "Find", "Open",
+  "Paste", "Props", "Select", "Undo", "Hiragana", "Katakana", "Unidentified",
+];
+
+/// Whether the value can be found in `KeyboardEvent.key`, ignoring case.
+///
+/// Any single printable character is accepted, as that is what most keys produce.
+pub(crate) fn is_key_value(key: &str) -> bool {
+  let mut chars = key.chars();
+  if let (Some(c), None) = (chars.next(), chars.next()) {
+    return !c.is_control();
+  }
+
+  is_function_key(key)
+    || KEY_VALUES
+      .iter()
+      .any(|it| it.eq_ignore_ascii_case(key))
+}
+
+/// Whether the value can be found in `KeyboardEvent.code`, ignoring case.
+pub(crate) fn is_code_value(code: &str) -> bool {
+  let numbered = |prefix: &str, is_valid: fn(&char) -> bool| {
+    code
+      .get(..prefix.len())
+      .is_some_and(|it| it.eq_ignore_ascii_case(prefix))
+      && code.len() == prefix.len() + 1
+      && code[prefix.len()..].chars().all(|c| is_valid(&c))
+  };
+
+  numbered("Key", char::is_ascii_alphabetic)
+    || numbered("Digit", char::is_ascii_digit)
+    || numbered("Numpad", char::is_ascii_digit)
+    || is_function_key(code)
+    || CODE_VALUES
+      .iter()
+      .any(|it| it.eq_ignore_ascii_case(code))
+}
+
+/// `F1` to `F24`.
+fn is_function_key(value: &str) -> bool {
+  value
+    .strip_prefix(['F', 'f'])
+    .filter(|it| !it.starts_with('0') && it.bytes().all(|b| b.is_ascii_digit()))
+    .and_then(|it| it.parse::<u8>().ok())
+    .is_some_and(|it| (1..=24).contains(&it))
+}
+
+#[cfg(test)]
+mod test {
+  use super::{is_code_value, is_key_value};
+
+  #[test]
+  fn key_values() {
+    assert!(is_key_value("a"));
+    assert!(is_key_value("["));
+    assert!(is_key_value("ç"));
+    assert!(is_key_value("F12"));
+    assert!(is_key_value("F24"));
+    assert!(is_key_value("Enter"));
+    assert!(is_key_value("arrowleft"));
+    assert!(!is_key_value("F25"));
+    assert!(!is_key_value("F01"));
+    assert!(!is_key_value("F+1"));
+    assert!(!is_key_value("Contol"));
+    assert!(!is_key_value("\n"));
+  }
+
+  #[test]
+  fn code_values() {
+    assert!(is_code_value("KeyF"));
+    assert!(is_code_value("keyz"));
+    assert!(is_code_value("Digit0"));
+    assert!(is_code_value("Numpad9"));
+    assert!(is_code_value("NumpadAdd"));
+    assert!(is_code_value("F5"));
+    assert!(is_code_value("BracketLeft"));
+    assert!(!is_code_value("Key1"));
+    assert!(!is_code_value("KeyAB"));
+    assert!(!is_code_value("Digit"));
+    assert!(!is_code_value("f"));
+  }
+}
@@ -0,0 +1,5 @@
+/// Used with `skip_serializing_if` to leave out flags that are not set.
+#[allow(clippy::trivially_copy_pass_by_ref)]
+pub(crate) fn is_false(value: &bool) -> bool {
+  !value
+}